@@ -1,6 +1,5 @@
 use bevy::prelude::*;
 use bevy_third_person_camera::*;
-use std::f32::consts::{E, PI};
 
 fn main() {
     App::new()
@@ -31,9 +30,6 @@ fn spawn_player(mut commands: Commands, assets: Res<AssetServer>) {
     commands.spawn(player);
 }
 
-// TODO: fix the location of the focus in terms of the actual world, otherwise it seems alright
-// with the debug info calls it shows that the functions and things are now able to modify both the
-// camera focus and the displacement via the data defined in the struct
 fn spawn_camera(mut commands: Commands) {
     let camera = (
         Camera3dBundle {
@@ -49,16 +45,34 @@ fn spawn_camera(mut commands: Commands) {
             zoom: Zoom::new(1.5, 5.0),
             offset_enabled: true,
             offset: Offset::new(0.4, 0.0),
-            focus_modifier: Some(CameraFocusModifier {
-                lower_threshold: PI / 2.,
-                upper_threshold: 2. * PI / 3.,
-                max_forward_displacement: 0.5,
-                max_backward_displacement: 0.75,
-                // typical logistic function centered at 0.5
-                lower_displacement_function: |x| 1. / (1. + E.powf(-15. * (x - 0.5))),
-                upper_displacement_function: |x| 1. / (1. + E.powf(-15. * (x - 0.5))),
-                behind_radius_displacement: 2.0,
-                lower_radius_function: |x| 1. - E.powf(-4. * x),
+            // turn the player to face the direction it moves in, at most 540 degrees per second
+            facing: TargetFacing {
+                policy: FacingPolicy::Movement,
+                turn_rate: 540.0,
+                ..default()
+            },
+            rigs: Some(CameraRigs {
+                // high above the target, looking down
+                top: CameraRig {
+                    radius: 1.3,
+                    height: 0.3,
+                    offset: (0.4, 0.0),
+                    fov: 50.0,
+                },
+                middle: CameraRig {
+                    radius: 1.0,
+                    height: 0.0,
+                    offset: (0.4, 0.0),
+                    fov: 45.0,
+                },
+                // low behind the target, looking up
+                bottom: CameraRig {
+                    radius: 0.5,
+                    height: -0.3,
+                    offset: (0.4, 0.0),
+                    fov: 40.0,
+                },
+                blend: DisplacementCurve::Smoothstep,
             }),
             ..default()
         },
@@ -94,46 +108,37 @@ fn spawn_world(
 fn player_movement_keyboard(
     time: Res<Time>,
     keys: Res<Input<KeyCode>>,
-    mut player_q: Query<(&mut Transform, &Speed), With<Player>>,
-    cam_q: Query<&Transform, (With<Camera3d>, Without<Player>)>,
+    mut player_q: Query<(&mut Transform, &Speed, &CameraBasis), With<Player>>,
 ) {
-    for (mut player_transform, player_speed) in player_q.iter_mut() {
-        let cam = match cam_q.get_single() {
-            Ok(c) => c,
-            Err(e) => Err(format!("Error retrieving camera: {}", e)).unwrap(),
-        };
-
-        let mut direction = Vec2::ZERO;
+    for (mut player_transform, player_speed, basis) in player_q.iter_mut() {
+        let mut input = Vec2::ZERO;
 
         // forward
         if keys.pressed(KeyCode::W) {
-            direction += cam.forward().xz().normalize();
+            input.y += 1.0;
         }
 
         // back
         if keys.pressed(KeyCode::S) {
-            direction += cam.back().xz().normalize();
+            input.y -= 1.0;
         }
 
         // left
         if keys.pressed(KeyCode::A) {
-            direction += cam.left().xz().normalize();
+            input.x -= 1.0;
         }
 
         // right
         if keys.pressed(KeyCode::D) {
-            direction += cam.right().xz().normalize();
+            input.x += 1.0;
         }
 
-        let movement = direction * player_speed.0 * time.delta_seconds();
-        player_transform.translation.x += movement.x;
-        player_transform.translation.z += movement.y;
-        let direction: Vec3 = (direction.x, 0.0, direction.y).into();
-
-        // rotate player to face direction he is currently moving
-        if direction.length_squared() > 0.0 {
-            player_transform.look_to(direction, Vec3::Y);
-        }
+        move_player(
+            &mut player_transform,
+            basis.move_direction(input),
+            player_speed,
+            &time,
+        );
     }
 }
 
@@ -141,8 +146,7 @@ fn player_movement_gamepad(
     time: Res<Time>,
     axis: Res<Axis<GamepadAxis>>,
     gamepad_res: Option<Res<GamepadResource>>,
-    mut player_q: Query<(&mut Transform, &Speed), With<Player>>,
-    cam_q: Query<&Transform, (With<Camera3d>, Without<Player>)>,
+    mut player_q: Query<(&mut Transform, &Speed, &CameraBasis), With<Player>>,
 ) {
     let gamepad = if let Some(gp) = gamepad_res {
         gp.0
@@ -150,44 +154,29 @@ fn player_movement_gamepad(
         return;
     };
 
-    let Ok(cam) = cam_q.get_single() else {
+    let x_axis = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX);
+    let y_axis = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY);
+    let (Some(x), Some(y)) = (axis.get(x_axis), axis.get(y_axis)) else {
         return;
     };
 
-    for (mut player_transform, player_speed) in player_q.iter_mut() {
-        let x_axis = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX);
-        let y_axis = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY);
-
-        let deadzone = 0.5;
-        let mut direction = Vec2::ZERO;
-        if let (Some(x), Some(y)) = (axis.get(x_axis), axis.get(y_axis)) {
-            if x.abs() > deadzone || y.abs() > deadzone {
-                if y > deadzone {
-                    // north
-                    direction += y * cam.forward().xz().normalize();
-                }
-                if y < deadzone {
-                    // south
-                    direction -= y * cam.back().xz().normalize();
-                }
-                if x > deadzone {
-                    // east
-                    direction += x * cam.right().xz().normalize();
-                }
-                if x < deadzone {
-                    // west
-                    direction -= x * cam.left().xz().normalize();
-                }
-            }
-
-            let movement = direction * player_speed.0 * time.delta_seconds();
-            player_transform.translation.x += movement.x;
-            player_transform.translation.z += movement.y;
-            let direction: Vec3 = (direction.x, 0.0, direction.y).into();
-
-            if direction.length_squared() > 0.0 {
-                player_transform.look_to(direction, Vec3::Y);
-            }
-        }
+    // ignore small deflections, then rescale so movement starts from zero at the deadzone's edge
+    let input = StickSettings {
+        deadzone: 0.2,
+        ..default()
+    }
+    .apply(Vec2::new(x, y));
+
+    for (mut player_transform, player_speed, basis) in player_q.iter_mut() {
+        move_player(
+            &mut player_transform,
+            basis.move_direction(input),
+            player_speed,
+            &time,
+        );
     }
 }
+
+fn move_player(player_transform: &mut Transform, direction: Vec3, speed: &Speed, time: &Time) {
+    player_transform.translation += direction * speed.0 * time.delta_seconds();
+}
@@ -36,7 +36,15 @@ fn spawn_camera(mut commands: Commands) {
             transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..default()
         },
-        ThirdPersonCamera::default(), // ADD THIS
+        // ADD THIS
+        ThirdPersonCamera {
+            // turn the player to face the direction it moves in
+            facing: TargetFacing {
+                policy: FacingPolicy::Movement,
+                ..default()
+            },
+            ..default()
+        },
     );
     commands.spawn(camera);
 }
@@ -69,44 +77,33 @@ fn spawn_world(
 fn player_movement(
     time: Res<Time>,
     keys: Res<Input<KeyCode>>,
-    mut player_q: Query<(&mut Transform, &Speed), With<Player>>,
-    cam_q: Query<&Transform, (With<Camera3d>, Without<Player>)>,
+    mut player_q: Query<(&mut Transform, &Speed, &CameraBasis), With<Player>>,
 ) {
-    for (mut player_transform, player_speed) in player_q.iter_mut() {
-        let cam = match cam_q.get_single() {
-            Ok(c) => c,
-            Err(e) => Err(format!("Error retrieving camera: {}", e)).unwrap(),
-        };
-
-        let mut direction = Vec3::ZERO;
+    for (mut player_transform, player_speed, basis) in player_q.iter_mut() {
+        let mut input = Vec2::ZERO;
 
         // forward
         if keys.pressed(KeyCode::W) {
-            direction += cam.forward();
+            input.y += 1.0;
         }
 
         // back
         if keys.pressed(KeyCode::S) {
-            direction += cam.back();
+            input.y -= 1.0;
         }
 
         // left
         if keys.pressed(KeyCode::A) {
-            direction += cam.left();
+            input.x -= 1.0;
         }
 
         // right
         if keys.pressed(KeyCode::D) {
-            direction += cam.right();
+            input.x += 1.0;
         }
 
-        direction.y = 0.0;
-        let movement = direction.normalize_or_zero() * player_speed.0 * time.delta_seconds();
-        player_transform.translation += movement;
-
-        // rotate player to face direction he is currently moving
-        if direction.length_squared() > 0.0 {
-            player_transform.look_to(direction, Vec3::Y);
-        }
+        // CameraBasis is kept up to date on the ThirdPersonCameraTarget by the plugin
+        let direction = basis.move_direction(input);
+        player_transform.translation += direction * player_speed.0 * time.delta_seconds();
     }
 }
@@ -0,0 +1,172 @@
+use bevy::{input::InputSystem, prelude::*};
+
+use crate::{device::active_button, GamepadResource, ThirdPersonCamera};
+
+pub struct AimPlugin;
+
+impl Plugin for AimPlugin {
+    fn build(&self, app: &mut App) {
+        // runs before `Update` so every camera system sees the same aim state in a frame
+        app.add_systems(PreUpdate, update_aim_state.after(InputSystem));
+    }
+}
+
+/// How the aim buttons, `aim_button` and `gamepad_settings.aim_button`, start and stop aiming
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect)]
+pub enum AimMode {
+    /// Aim while the button is held
+    #[default]
+    Hold,
+    /// Each press switches aiming on or off
+    Toggle,
+    /// A press shorter than `tap_time` seconds toggles aiming on, a longer press aims while held.
+    /// Any press while toggled on stops aiming
+    Hybrid { tap_time: f32 },
+}
+
+/// Whether the camera is currently aiming. Updated from the aim buttons according to the
+/// camera's [`AimMode`], and read by zooming, the shoulder offset and target facing
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect)]
+pub struct AimState {
+    active: bool,
+    toggled: bool,
+    was_pressed: bool,
+    // seconds the button has been held, `None` when not held or when the press ended a toggle
+    press_time: Option<f32>,
+}
+
+impl AimState {
+    /// Whether the camera is aiming
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // steps the state with whether any aim button is pressed this frame
+    fn update(&mut self, mode: AimMode, pressed: bool, delta_seconds: f32) {
+        let just_pressed = pressed && !self.was_pressed;
+        let just_released = !pressed && self.was_pressed;
+        self.was_pressed = pressed;
+
+        match mode {
+            AimMode::Hold => {
+                self.toggled = false;
+                self.active = pressed;
+            }
+            AimMode::Toggle => {
+                if just_pressed {
+                    self.toggled = !self.toggled;
+                }
+                self.active = self.toggled;
+            }
+            AimMode::Hybrid { tap_time } => {
+                if just_pressed {
+                    if self.toggled {
+                        self.toggled = false;
+                        self.press_time = None;
+                    } else {
+                        self.press_time = Some(0.0);
+                    }
+                } else if let Some(press_time) = self.press_time.as_mut() {
+                    *press_time += delta_seconds;
+                }
+
+                if just_released {
+                    if self
+                        .press_time
+                        .is_some_and(|press_time| press_time < tap_time)
+                    {
+                        self.toggled = true;
+                    }
+                    self.press_time = None;
+                }
+                self.active = self.toggled || self.press_time.is_some();
+            }
+        }
+    }
+}
+
+// steps the camera's `AimState`, treating the mouse and gamepad aim buttons the same way
+fn update_aim_state(
+    mut cam_q: Query<&mut ThirdPersonCamera>,
+    mouse: Res<Input<MouseButton>>,
+    btns: Res<Input<GamepadButton>>,
+    gamepad_res: Option<Res<GamepadResource>>,
+    time: Res<Time>,
+) {
+    let Ok(mut cam) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    if !cam.aim_enabled {
+        if cam.aim_state != AimState::default() {
+            cam.aim_state = AimState::default();
+        }
+        return;
+    }
+
+    let aim_button = active_button(gamepad_res.as_deref(), cam.gamepad_settings.aim_button);
+    let pressed = mouse.pressed(cam.aim_button) || btns.pressed(aim_button);
+    let mut state = cam.aim_state;
+    state.update(cam.aim_mode, pressed, time.delta_seconds());
+    if state != cam.aim_state {
+        cam.aim_state = state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{test_app, AimMode, ThirdPersonCamera};
+
+    #[test]
+    fn hybrid_aim_taps_toggle_and_holds_aim_while_held() {
+        let mut app = test_app(Duration::from_millis(100));
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    aim_enabled: true,
+                    aim_mode: AimMode::Hybrid { tap_time: 0.25 },
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        let button = app
+            .world
+            .get::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .gamepad_settings
+            .aim_button;
+        let set_pressed = |app: &mut App, pressed: bool| {
+            let mut buttons = app.world.resource_mut::<Input<GamepadButton>>();
+            if pressed {
+                buttons.press(button);
+            } else {
+                buttons.release(button);
+            }
+            app.update();
+            app.world
+                .get::<ThirdPersonCamera>(cam)
+                .unwrap()
+                .aim_state
+                .is_active()
+        };
+
+        // a tap toggles aiming on, the next press turns it off
+        assert!(set_pressed(&mut app, true));
+        assert!(set_pressed(&mut app, false));
+        assert!(!set_pressed(&mut app, true));
+        assert!(!set_pressed(&mut app, false));
+
+        // holding past the tap time aims only while held
+        assert!(set_pressed(&mut app, true));
+        for _ in 0..3 {
+            assert!(set_pressed(&mut app, true));
+        }
+        assert!(!set_pressed(&mut app, false));
+    }
+}
@@ -0,0 +1,166 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::up::yaw_between;
+
+/// Aim assist for gamepads while aiming. The camera turns slower while the reticle is over an
+/// [`AimAssistTarget`] and is gently pulled towards the nearest target inside a cone around the
+/// reticle. The pull only happens while the stick is moved, so the camera never aims by itself.
+/// Mouse input is never assisted
+///
+/// The cone is angular, with its tip at the camera and centred on the camera's view axis. With
+/// the shoulder offset that axis is the offset reticle ray, the same ray
+/// [`CameraAim::point`](crate::CameraAim::point) lies on, not a ray through the focus
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{AimAssist, CustomGamepadSettings, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             aim_enabled: true,
+///             gamepad_settings: CustomGamepadSettings {
+///                 aim_assist: Some(AimAssist {
+///                     strength: 1.0,
+///                     slowdown: 0.4,
+///                     magnetism: 10.0,
+///                     cone_angle: 8.0,
+///                     falloff: 1.0,
+///                     max_distance: 50.0,
+///                 }),
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, Reflect)]
+pub struct AimAssist {
+    /// Scales both the slowdown and the pull, 0 turns aim assist off and 1 is full strength
+    pub strength: f32,
+    /// Fraction of the stick's turning speed taken away while the reticle is over a target
+    pub slowdown: f32,
+    /// Degrees per second the camera is pulled towards a target at the center of the cone
+    pub magnetism: f32,
+    /// Half angle, in degrees, of the cone around the camera's view axis in which targets pull the
+    /// camera
+    pub cone_angle: f32,
+    /// How quickly the pull weakens towards the edge of the cone. 1 is linear, higher values
+    /// keep the pull close to the center
+    pub falloff: f32,
+    /// Targets further away than this are ignored
+    pub max_distance: f32,
+}
+
+impl Default for AimAssist {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            slowdown: 0.4,
+            magnetism: 10.0,
+            cone_angle: 8.0,
+            falloff: 1.0,
+            max_distance: 50.0,
+        }
+    }
+}
+
+/// Marks an entity that gamepad [`AimAssist`] slows down over and pulls towards. `radius` is
+/// the size of the sphere, around the entity's [`GlobalTransform`], that counts as being under
+/// the reticle
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::AimAssistTarget;
+/// fn spawn_enemy(mut commands: Commands) {
+///     commands.spawn((
+///         PbrBundle::default(),
+///         AimAssistTarget { radius: 0.5 },
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy)]
+pub struct AimAssistTarget {
+    pub radius: f32,
+}
+
+impl Default for AimAssistTarget {
+    fn default() -> Self {
+        Self { radius: 0.5 }
+    }
+}
+
+// pitch of a normalized direction relative to `up`, positive when looking up
+fn pitch(direction: Vec3, up: Vec3) -> f32 {
+    direction.dot(up).clamp(-1.0, 1.0).asin()
+}
+
+impl AimAssist {
+    // scales the stick's yaw & pitch while the reticle is over a target and adds the pull towards
+    // the nearest target in the cone. The cone is around `transform`'s forward from its
+    // translation, which already includes the shoulder offset. `targets` are positions and radii,
+    // `turn` is this frame's yaw & pitch from the stick and `stick` how far it is deflected, from
+    // 0 to 1
+    pub(crate) fn apply(
+        &self,
+        transform: &Transform,
+        up: Vec3,
+        targets: impl IntoIterator<Item = (Vec3, f32)>,
+        turn: Vec2,
+        stick: f32,
+        delta_seconds: f32,
+    ) -> Vec2 {
+        let origin = transform.translation;
+        let forward = transform.forward();
+        let cone = self.cone_angle.to_radians().clamp(0.0, PI);
+
+        let mut over_target = false;
+        // direction and angle off the reticle of the nearest target in the cone
+        let mut nearest: Option<(Vec3, f32)> = None;
+        for (position, radius) in targets {
+            let to_target = position - origin;
+            let distance = to_target.length();
+            if distance <= f32::EPSILON || distance > self.max_distance {
+                continue;
+            }
+            let direction = to_target / distance;
+            let angle = forward.angle_between(direction);
+
+            if angle <= (radius / distance).atan() {
+                over_target = true;
+            }
+            if angle <= cone && !nearest.is_some_and(|(_, nearest)| nearest <= angle) {
+                nearest = Some((direction, angle));
+            }
+        }
+
+        let strength = self.strength.clamp(0.0, 1.0);
+        let mut turn = turn;
+        if over_target {
+            turn *= 1.0 - self.slowdown.clamp(0.0, 1.0) * strength;
+        }
+
+        if let Some((direction, angle)) = nearest {
+            let weight = (1.0 - angle / cone.max(f32::EPSILON))
+                .clamp(0.0, 1.0)
+                .powf(self.falloff.max(0.0));
+            let pull = self.magnetism.to_radians() * weight * strength * stick.clamp(0.0, 1.0);
+
+            // shortest yaw & pitch that would put the target under the reticle
+            let error = Vec2::new(
+                yaw_between(forward, direction, up),
+                pitch(direction, up) - pitch(forward, up),
+            );
+            turn += error.clamp_length_max(pull * delta_seconds);
+        }
+
+        turn
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+
+use crate::{orbit, smooth, up::yaw_between, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+/// Chase camera for vehicles. Above `min_speed` the camera swings around behind the direction the
+/// target is moving, lagging behind it. Orbiting with the mouse or a gamepad looks around freely,
+/// and the camera returns behind the target once there has been no input for `free_look_delay`
+/// seconds. The camera also banks into turns
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{ChaseCamera, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     let mut chase = ChaseCamera::default();
+///     chase.min_speed = 3.0;
+///     chase.max_bank = 5.0;
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             chase: Some(chase),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct ChaseCamera {
+    /// Speed, in units per second, above which the camera follows the direction of movement
+    pub min_speed: f32,
+    /// How quickly the camera swings behind the direction of movement and settles its bank.
+    /// Higher values lag less
+    pub follow_speed: f32,
+    /// Seconds without orbit input before the camera returns behind the target
+    pub free_look_delay: f32,
+    /// Degrees of bank per degree per second that the direction of movement turns
+    pub bank: f32,
+    /// Maximum bank, in degrees
+    pub max_bank: f32,
+    // seconds since the player last orbited, `None` if they haven't yet
+    since_free_look: Option<f32>,
+    last_position: Option<Vec3>,
+    heading: Option<Vec3>,
+    following: bool,
+    // current bank in radians, positive when banking left
+    bank_angle: f32,
+}
+
+impl Default for ChaseCamera {
+    fn default() -> Self {
+        Self {
+            min_speed: 2.0,
+            follow_speed: 2.0,
+            free_look_delay: 1.5,
+            bank: 0.1,
+            max_bank: 8.0,
+            since_free_look: None,
+            last_position: None,
+            heading: None,
+            following: false,
+            bank_angle: 0.0,
+        }
+    }
+}
+
+impl ChaseCamera {
+    /// True while the camera is swinging behind the direction of movement, false while the target
+    /// is too slow or the player is looking around
+    pub fn is_following(&self) -> bool {
+        self.following
+    }
+
+    // orbit input pauses following for `free_look_delay`
+    pub(crate) fn free_look(&mut self) {
+        self.since_free_look = Some(0.0);
+    }
+
+    // the bank as a roll around the camera's forward axis
+    pub(crate) fn roll(&self) -> Quat {
+        Quat::from_rotation_z(self.bank_angle)
+    }
+}
+
+// only run chase logic if `chase` is set
+pub(crate) fn chase_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.chase.is_some()
+}
+
+// turns the camera behind the target's direction of movement and banks it into turns. The bank
+// is only applied to the camera's `GlobalTransform`, so orbiting isn't affected by it
+pub(crate) fn chase(
+    mut cam_q: Query<(&mut ThirdPersonCamera, &mut Transform), Without<ThirdPersonCameraTarget>>,
+    target_q: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+    time: Res<Time>,
+) {
+    let Ok((mut cam, mut cam_transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+    let Ok(target) = target_q.get_single() else {
+        return;
+    };
+
+    let up = cam.up.current();
+    let delta_seconds = time.delta_seconds();
+    let chase = cam.chase.as_mut().unwrap();
+
+    let position = target.translation();
+    let last_position = chase.last_position.replace(position);
+    if delta_seconds <= 0.0 {
+        return;
+    }
+    if let Some(since_free_look) = chase.since_free_look.as_mut() {
+        *since_free_look += delta_seconds;
+    }
+
+    // direction of movement along the ground, only while moving fast enough
+    let velocity = last_position.map_or(Vec3::ZERO, |last| (position - last) / delta_seconds);
+    let velocity = velocity.reject_from(up);
+    let heading = (velocity.length() > chase.min_speed).then(|| velocity.normalize());
+    let turn_rate = match (chase.heading, heading) {
+        (Some(last), Some(heading)) => yaw_between(last, heading, up) / delta_seconds,
+        _ => 0.0,
+    };
+    chase.heading = heading;
+
+    let free_look = chase
+        .since_free_look
+        .is_some_and(|since_free_look| since_free_look < chase.free_look_delay);
+    chase.following = heading.is_some() && !free_look;
+
+    let follow_speed = chase.follow_speed;
+    let follow = |current, target| smooth(current, target, follow_speed, delta_seconds);
+
+    let mut bank = 0.0;
+    if let (Some(heading), false) = (heading, free_look) {
+        let yaw = follow(0.0, yaw_between(cam_transform.forward(), heading, up));
+        orbit(&mut cam_transform.rotation, up, yaw, 0.0);
+
+        let max_bank = chase.max_bank.max(0.0);
+        bank = (turn_rate.to_degrees() * chase.bank)
+            .clamp(-max_bank, max_bank)
+            .to_radians();
+    }
+    chase.bank_angle = follow(chase.bank_angle, bank);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{input::mouse::MouseMotion, prelude::*};
+
+    use crate::{test_app, ChaseCamera, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+    #[test]
+    fn follows_movement_pauses_for_free_look_and_banks() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    chase: Some(ChaseCamera::default()),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world.entity_mut(cam).insert(GlobalTransform::default());
+        // moves the target by a tenth of a second of `velocity`
+        let drive = |app: &mut App, velocity: Vec3| {
+            let mut transform = app.world.get_mut::<GlobalTransform>(target).unwrap();
+            *transform =
+                GlobalTransform::from_translation(transform.translation() + velocity * 0.1);
+            app.update();
+        };
+        let forward = |app: &App| app.world.get::<Transform>(cam).unwrap().forward();
+        app.update();
+
+        // driving along +x at 10 units per second turns the camera to face +x
+        for _ in 0..40 {
+            drive(&mut app, Vec3::X * 10.0);
+        }
+        assert!(forward(&app).abs_diff_eq(Vec3::X, 1e-2));
+
+        // looking around stops the camera from following until the delay has passed
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(500.0, 0.0),
+        });
+        drive(&mut app, Vec3::X * 10.0);
+        let looked = forward(&app);
+        assert!(looked.angle_between(Vec3::X) > 1.0);
+        drive(&mut app, Vec3::X * 10.0);
+        assert!(forward(&app).abs_diff_eq(looked, 1e-4));
+        for _ in 0..60 {
+            drive(&mut app, Vec3::X * 10.0);
+        }
+        assert!(forward(&app).abs_diff_eq(Vec3::X, 1e-2));
+
+        // turning left banks the rendered camera left, its transform stays level
+        let mut velocity = Vec3::X * 10.0;
+        for _ in 0..10 {
+            velocity = Quat::from_rotation_y(0.1) * velocity;
+            drive(&mut app, velocity);
+        }
+        let transform = app.world.get::<Transform>(cam).unwrap();
+        let global = app.world.get::<GlobalTransform>(cam).unwrap();
+        assert!(global.up().dot(transform.left()) > 0.05);
+        assert!(transform.left().y.abs() < 1e-4);
+    }
+}
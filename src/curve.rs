@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use bevy::reflect::Reflect;
+
+/// Maps an input from 0 to 1 to an output, used by the
+/// [`CameraFocusModifier`](crate::CameraFocusModifier) to shape how far the focus and the camera
+/// move. Apart from `Custom`, curves are plain data, so they can be tweaked at runtime through
+/// [`Reflect`] and, with the `serde` feature, loaded from assets. The function of a `Custom` curve
+/// is ignored by reflection, a copy made through reflection outputs 0
+///
+/// # Examples
+///
+/// ```
+/// use bevy_third_person_camera::DisplacementCurve;
+///
+/// let logistic = DisplacementCurve::Logistic {
+///     steepness: 15.0,
+///     midpoint: 0.5,
+/// };
+/// assert_eq!(logistic.sample(0.5), 0.5);
+///
+/// assert_eq!(DisplacementCurve::Smoothstep.sample(0.25), 0.15625);
+///
+/// // straight lines between keyframes of (input, output), flat past the first and last one
+/// let keyframes = DisplacementCurve::Keyframes(vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.5)]);
+/// assert_eq!(keyframes.sample(0.25), 0.5);
+/// assert_eq!(keyframes.sample(0.75), 0.75);
+///
+/// // a smooth curve through the same keyframes
+/// let spline = DisplacementCurve::Spline(vec![(0.0, 0.0), (0.5, 1.0), (1.0, 0.5)]);
+/// assert_eq!(spline.sample(0.5), 1.0);
+///
+/// let custom = DisplacementCurve::custom(|x| x * x);
+/// assert_eq!(custom.sample(0.5), 0.25);
+/// ```
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplacementCurve {
+    /// The same output for every input
+    Constant(f32),
+    /// Outputs the input
+    Linear,
+    /// S-shaped curve, `1 / (1 + e^(-steepness * (x - midpoint)))`
+    Logistic { steepness: f32, midpoint: f32 },
+    /// Eases in and out, `3x^2 - 2x^3`
+    Smoothstep,
+    /// Rises quickly and levels off, `1 - e^(-rate * x)`
+    Exponential { rate: f32 },
+    /// Straight lines between keyframes of (input, output), sorted by input
+    Keyframes(Vec<(f32, f32)>),
+    /// A smooth curve through keyframes of (input, output), sorted by input
+    Spline(Vec<(f32, f32)>),
+    /// Any function, for curves that need to capture state. The function doesn't survive a
+    /// round trip: it can't be serialized, and a `Custom` curve built through reflection, for
+    /// example cloned or edited from an inspector, outputs 0 for every input
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(
+        #[reflect(ignore, default = "crate::unreflected_curve")]
+        Arc<dyn Fn(f32) -> f32 + Send + Sync>,
+    ),
+}
+
+impl Default for DisplacementCurve {
+    fn default() -> Self {
+        DisplacementCurve::Constant(0.0)
+    }
+}
+
+impl DisplacementCurve {
+    /// Wraps a function or closure as a curve
+    pub fn custom(curve: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        DisplacementCurve::Custom(Arc::new(curve))
+    }
+
+    /// The output of the curve at `x`
+    pub fn sample(&self, x: f32) -> f32 {
+        match self {
+            DisplacementCurve::Constant(value) => *value,
+            DisplacementCurve::Linear => x,
+            DisplacementCurve::Logistic {
+                steepness,
+                midpoint,
+            } => 1.0 / (1.0 + (-steepness * (x - midpoint)).exp()),
+            DisplacementCurve::Smoothstep => {
+                let x = x.clamp(0.0, 1.0);
+                x * x * (3.0 - 2.0 * x)
+            }
+            DisplacementCurve::Exponential { rate } => 1.0 - (-rate * x).exp(),
+            DisplacementCurve::Keyframes(keyframes) => sample_keyframes(keyframes, x, false),
+            DisplacementCurve::Spline(keyframes) => sample_keyframes(keyframes, x, true),
+            DisplacementCurve::Custom(curve) => curve(x),
+        }
+    }
+}
+
+// interpolates between the keyframes around `x`, either linearly or with a cubic hermite spline
+// whose tangents come from the neighbouring keyframes
+fn sample_keyframes(keyframes: &[(f32, f32)], x: f32, smooth: bool) -> f32 {
+    let (Some(first), Some(last)) = (keyframes.first(), keyframes.last()) else {
+        return 0.0;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+
+    let i = keyframes.partition_point(|(input, _)| *input <= x).max(1) - 1;
+    let (x0, y0) = keyframes[i];
+    let (x1, y1) = keyframes[i + 1];
+    let width = x1 - x0;
+    if width <= f32::EPSILON {
+        return y1;
+    }
+    let t = (x - x0) / width;
+    if !smooth {
+        return y0 + (y1 - y0) * t;
+    }
+
+    let slope = |i: usize| {
+        let (before, after) = (
+            keyframes[i.saturating_sub(1)],
+            keyframes[(i + 1).min(keyframes.len() - 1)],
+        );
+        let run = after.0 - before.0;
+        if run <= f32::EPSILON {
+            0.0
+        } else {
+            (after.1 - before.1) / run
+        }
+    };
+    let (m0, m1) = (slope(i) * width, slope(i + 1) * width);
+    let (t2, t3) = (t * t, t * t * t);
+    (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+        + (t3 - 2.0 * t2 + t) * m0
+        + (-2.0 * t3 + 3.0 * t2) * y1
+        + (t3 - t2) * m1
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::{FromReflect, GetPath, Reflect};
+
+    use super::DisplacementCurve;
+
+    #[test]
+    fn keyframes_can_be_edited_through_reflection() {
+        let mut curve = DisplacementCurve::Keyframes(vec![(0.0, 0.0), (1.0, 1.0)]);
+        // the output of the second keyframe
+        *curve.path_mut::<f32>(".0[1].1").unwrap() = 0.5;
+        assert_eq!(curve.sample(1.0), 0.5);
+    }
+
+    #[test]
+    fn custom_curves_lose_their_function_through_reflection() {
+        let custom = DisplacementCurve::custom(|x| x * x);
+        let copy = DisplacementCurve::from_reflect(custom.as_reflect()).unwrap();
+        assert_eq!(copy.sample(0.5), 0.0);
+    }
+}
@@ -0,0 +1,211 @@
+use bevy::{
+    input::{
+        gamepad::{GamepadAxisChangedEvent, GamepadConnection, GamepadConnectionEvent},
+        mouse::{MouseMotion, MouseWheel},
+        InputSystem,
+    },
+    prelude::*,
+};
+
+use crate::GamepadResource;
+
+// how far a stick has to be pushed for the gamepad to count as being used
+const STICK_ACTIVITY: f32 = 0.5;
+
+pub struct DevicePlugin;
+
+impl Plugin for DevicePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastInputDevice>().add_systems(
+            PreUpdate,
+            (connections, track_last_device).chain().after(InputSystem),
+        );
+    }
+}
+
+/// The device the player used most recently, for example to switch button prompts between
+/// keyboard & mouse and gamepad
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::LastInputDevice;
+/// fn show_prompts(device: Res<LastInputDevice>) {
+///     if device.is_changed() {
+///         match *device {
+///             LastInputDevice::MouseKeyboard => println!("Press E"),
+///             LastInputDevice::Gamepad(_) => println!("Press X"),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum LastInputDevice {
+    #[default]
+    MouseKeyboard,
+    Gamepad(Gamepad),
+}
+
+// returns `button` on the active gamepad, or as configured if no gamepad is active
+pub(crate) fn active_button(
+    gamepad_res: Option<&GamepadResource>,
+    button: GamepadButton,
+) -> GamepadButton {
+    match gamepad_res {
+        Some(gamepad) => GamepadButton::new(gamepad.0, button.button_type),
+        None => button,
+    }
+}
+
+// keeps `GamepadResource` on the gamepad that last pressed a button. When the active gamepad
+// disconnects another connected one takes over, and the resource is only removed once no
+// gamepads are left
+fn connections(
+    mut cmds: Commands,
+    gamepad_res: Option<Res<GamepadResource>>,
+    gamepads: Res<Gamepads>,
+    btns: Res<Input<GamepadButton>>,
+    mut gamepad_evr: EventReader<GamepadConnectionEvent>,
+) {
+    let current = gamepad_res.map(|gp| gp.0);
+    let mut active = current;
+
+    for ev in gamepad_evr.read() {
+        match &ev.connection {
+            GamepadConnection::Connected(_info) => {
+                // if no gamepad is setup yet, use this one
+                if active.is_none() {
+                    active = Some(ev.gamepad);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                // fall back to any other connected gamepad
+                if active == Some(ev.gamepad) {
+                    active = gamepads.iter().find(|gamepad| *gamepad != ev.gamepad);
+                }
+            }
+        }
+    }
+
+    // the gamepad that pressed a button last becomes the active one
+    if let Some(button) = btns.get_just_pressed().last() {
+        active = Some(button.gamepad);
+    }
+
+    if active != current {
+        match active {
+            Some(gamepad) => cmds.insert_resource(GamepadResource(gamepad)),
+            None => cmds.remove_resource::<GamepadResource>(),
+        }
+    }
+}
+
+// records whether the mouse & keyboard or a gamepad was used last
+fn track_last_device(
+    mut last_device: ResMut<LastInputDevice>,
+    keys: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    btns: Res<Input<GamepadButton>>,
+    mut motion_evr: EventReader<MouseMotion>,
+    mut wheel_evr: EventReader<MouseWheel>,
+    mut axis_evr: EventReader<GamepadAxisChangedEvent>,
+) {
+    let mut device = None;
+
+    if let Some(ev) = axis_evr
+        .read()
+        .filter(|ev| ev.value.abs() > STICK_ACTIVITY)
+        .last()
+    {
+        device = Some(LastInputDevice::Gamepad(ev.gamepad));
+    }
+    if let Some(button) = btns.get_just_pressed().last() {
+        device = Some(LastInputDevice::Gamepad(button.gamepad));
+    }
+
+    let mouse_moved = motion_evr
+        .read()
+        .filter(|ev| ev.delta != Vec2::ZERO)
+        .count()
+        > 0;
+    let scrolled = wheel_evr.read().count() > 0;
+    if mouse_moved
+        || scrolled
+        || keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+    {
+        device = Some(LastInputDevice::MouseKeyboard);
+    }
+
+    if let Some(device) = device {
+        if *last_device != device {
+            *last_device = device;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        input::{
+            gamepad::{
+                GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent,
+                GamepadInfo,
+            },
+            mouse::MouseMotion,
+        },
+        prelude::*,
+    };
+
+    use crate::{test_app, GamepadResource, LastInputDevice};
+
+    fn connection(gamepad: Gamepad, connection: GamepadConnection) -> GamepadEvent {
+        GamepadEvent::Connection(GamepadConnectionEvent::new(gamepad, connection))
+    }
+
+    fn connected() -> GamepadConnection {
+        GamepadConnection::Connected(GamepadInfo { name: "pad".into() })
+    }
+
+    #[test]
+    fn switches_to_the_last_used_gamepad() {
+        let mut app = test_app(Duration::from_millis(100));
+        let (first, second) = (Gamepad::new(0), Gamepad::new(1));
+        let active = |app: &App| app.world.get_resource::<GamepadResource>().map(|gp| gp.0);
+
+        app.world.send_event(connection(first, connected()));
+        app.world.send_event(connection(second, connected()));
+        app.update();
+        assert_eq!(active(&app), Some(first));
+
+        let press = GamepadButtonChangedEvent::new(second, GamepadButtonType::South, 1.0);
+        app.world.send_event(GamepadEvent::Button(press));
+        app.update();
+        assert_eq!(active(&app), Some(second));
+        assert_eq!(
+            *app.world.resource::<LastInputDevice>(),
+            LastInputDevice::Gamepad(second)
+        );
+
+        app.world.send_event(MouseMotion { delta: Vec2::X });
+        app.update();
+        assert_eq!(
+            *app.world.resource::<LastInputDevice>(),
+            LastInputDevice::MouseKeyboard
+        );
+
+        // disconnecting another gamepad keeps the active one
+        app.world
+            .send_event(connection(first, GamepadConnection::Disconnected));
+        app.update();
+        assert_eq!(active(&app), Some(second));
+
+        app.world
+            .send_event(connection(second, GamepadConnection::Disconnected));
+        app.update();
+        assert_eq!(active(&app), None);
+    }
+}
@@ -0,0 +1,400 @@
+use bevy::prelude::*;
+
+use crate::{
+    gamepad::orbit_gamepad, ground::clamp_to_ground, mouse::orbit_mouse, CameraBasis,
+    ThirdPersonCamera, ThirdPersonCameraSet, ThirdPersonCameraTarget,
+};
+
+pub struct FacingPlugin;
+
+impl Plugin for FacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, face_target.after(orbit_mouse).after(orbit_gamepad))
+            .add_systems(
+                PostUpdate,
+                publish_aim
+                    .after(clamp_to_ground)
+                    .in_set(ThirdPersonCameraSet),
+            );
+    }
+}
+
+/// Which way the [`ThirdPersonCameraTarget`] is turned to face
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+pub enum FacingPolicy {
+    /// Face the direction the target is moving in
+    Movement,
+    /// Always face the way the camera is looking, for strafing movement
+    Camera,
+    /// Face the way the camera is looking only while aiming
+    #[default]
+    CameraWhileAiming,
+    /// Never turn the target
+    None,
+}
+
+/// How the camera turns the [`ThirdPersonCameraTarget`]. The target only turns around the up
+/// axis, at most `turn_rate` degrees per second, so it rotates smoothly instead of snapping. The
+/// pitch of the camera is published in [`CameraAim`] instead
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{FacingPolicy, TargetFacing, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             facing: TargetFacing {
+///                 policy: FacingPolicy::Movement,
+///                 turn_rate: 540.0,
+///                 aim_distance: 10.0,
+///             },
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, Reflect)]
+pub struct TargetFacing {
+    pub policy: FacingPolicy,
+    /// Maximum degrees per second the target turns
+    pub turn_rate: f32,
+    /// Distance past the focus, along the camera's view, of [`CameraAim::point`]
+    pub aim_distance: f32,
+}
+
+impl Default for TargetFacing {
+    fn default() -> Self {
+        Self {
+            policy: FacingPolicy::default(),
+            turn_rate: 720.0,
+            aim_distance: 10.0,
+        }
+    }
+}
+
+/// Where the camera is aiming, kept up to date on the [`ThirdPersonCameraTarget`] for animation
+/// and IK systems, for example to bend the spine or turn the head while the target itself only
+/// turns around the up axis
+///
+/// Published in [`ThirdPersonCameraSet`] once the camera has been placed for the frame, so the
+/// aim point is always on the camera's view. Systems reading it should run after that set
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::CameraAim;
+/// fn bend_spine(player_q: Query<&CameraAim>, mut spine_q: Query<&mut Transform, With<Name>>) {
+///     let Ok(aim) = player_q.get_single() else {
+///         return;
+///     };
+///     for mut spine in spine_q.iter_mut() {
+///         let pitch = if aim.active { aim.pitch } else { 0.0 };
+///         spine.rotation = Quat::from_rotation_x(pitch * 0.5);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CameraAim {
+    /// Whether the camera is aiming, see [`AimState`](crate::AimState)
+    pub active: bool,
+    /// Camera pitch in radians, positive when looking up
+    pub pitch: f32,
+    /// World space point the camera is aiming at, `aim_distance` past the focus
+    pub point: Vec3,
+}
+
+// minimum speed, in units per second, the target has to move at before it turns to face its
+// movement
+const MIN_FACING_SPEED: f32 = 0.1;
+
+// rotates `from` towards `to` by at most `max_angle` radians
+fn rotate_towards(from: Quat, to: Quat, max_angle: f32) -> Quat {
+    let angle = from.angle_between(to);
+    if angle <= max_angle {
+        to
+    } else {
+        from.slerp(to, max_angle / angle)
+    }
+}
+
+// turns the target around the up axis according to the camera's `TargetFacing`. Movement is
+// measured in the target's parent frame, so a target standing still on a moving platform doesn't
+// turn, and facing is turned in world space
+fn face_target(
+    cam_q: Query<(&ThirdPersonCamera, &Transform), Without<ThirdPersonCameraTarget>>,
+    mut target_q: Query<(Entity, &mut Transform), With<ThirdPersonCameraTarget>>,
+    parent_q: Query<&Parent>,
+    global_q: Query<&GlobalTransform>,
+    time: Res<Time>,
+    mut last_position: Local<Option<(Option<Entity>, Vec3)>>,
+) {
+    let Ok((cam, cam_transform)) = cam_q.get_single() else {
+        return;
+    };
+    let Ok((target, mut target_transform)) = target_q.get_single_mut() else {
+        return;
+    };
+
+    let parent = parent_q.get(target).ok().map(Parent::get);
+    let parent_rotation = parent
+        .and_then(|parent| global_q.get(parent).ok())
+        .map_or(Quat::IDENTITY, |parent| {
+            parent.to_scale_rotation_translation().1
+        });
+
+    // the change in the local translation, rotated into world space. Reparenting moves the
+    // target to a different frame, so nothing is measured across it
+    let position = target_transform.translation;
+    let movement = match *last_position {
+        Some((last_parent, last)) if last_parent == parent => parent_rotation * (position - last),
+        _ => Vec3::ZERO,
+    };
+    *last_position = Some((parent, position));
+
+    let aiming = cam.aim_state.is_active();
+    let up = cam.up.current();
+
+    // the camera's forward flattened onto the ground, so the target doesn't tilt
+    let camera_forward = || CameraBasis::from_rotation_and_up(cam_transform.rotation, up).forward;
+    let direction = match cam.facing.policy {
+        FacingPolicy::Movement => {
+            let movement = movement.reject_from(up);
+            if movement.length() > MIN_FACING_SPEED * time.delta_seconds() {
+                movement
+            } else {
+                return;
+            }
+        }
+        FacingPolicy::Camera => camera_forward(),
+        FacingPolicy::CameraWhileAiming if aiming => camera_forward(),
+        FacingPolicy::CameraWhileAiming | FacingPolicy::None => return,
+    };
+
+    // the facing is turned in world space, then written back relative to the target's parent
+    let current = parent_rotation * target_transform.rotation;
+    let desired = Transform::default().looking_to(direction, up).rotation;
+    let max_angle = cam.facing.turn_rate.to_radians() * time.delta_seconds();
+    let rotation = parent_rotation.inverse() * rotate_towards(current, desired, max_angle);
+    if !rotation.abs_diff_eq(target_transform.rotation, 1e-6) {
+        target_transform.rotation = rotation;
+    }
+}
+
+// publishes the camera's `CameraAim` on the target, from the camera's final pose for the frame
+fn publish_aim(
+    mut commands: Commands,
+    cam_q: Query<(&ThirdPersonCamera, &Transform)>,
+    mut target_q: Query<(Entity, Option<&mut CameraAim>), With<ThirdPersonCameraTarget>>,
+) {
+    let Ok((cam, cam_transform)) = cam_q.get_single() else {
+        return;
+    };
+    let Ok((target, aim)) = target_q.get_single_mut() else {
+        return;
+    };
+
+    // the point on the camera's view the same distance in front of it as the focus, pushed out
+    // by `aim_distance`
+    let view = cam_transform.forward();
+    let depth = (cam.focus - cam_transform.translation).dot(view);
+    let new_aim = CameraAim {
+        active: cam.aim_state.is_active(),
+        pitch: view.dot(cam.up.current()).clamp(-1.0, 1.0).asin(),
+        point: cam_transform.translation + view * (depth + cam.facing.aim_distance),
+    };
+    match aim {
+        Some(mut aim) => {
+            if *aim != new_aim {
+                *aim = new_aim;
+            }
+        }
+        None => {
+            commands.entity(target).insert(new_aim);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::{input::mouse::MouseMotion, prelude::*, transform::TransformPlugin};
+
+    use crate::{
+        test_app, CameraAim, FacingPolicy, TargetFacing, ThirdPersonCamera, ThirdPersonCameraTarget,
+    };
+
+    #[test]
+    fn camera_policy_turns_the_target_at_the_turn_rate() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, SpatialBundle::default()))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    facing: TargetFacing {
+                        policy: FacingPolicy::Camera,
+                        turn_rate: 90.0,
+                        aim_distance: 10.0,
+                    },
+                    ..default()
+                },
+                // looking down the x axis, a quarter turn away from the target's facing, and 0.3
+                // radians up, which the target doesn't follow
+                Transform::from_rotation(
+                    Quat::from_rotation_y(-FRAC_PI_2) * Quat::from_rotation_x(0.3),
+                ),
+            ))
+            .id();
+
+        // the first update doesn't advance time, the next two turn 9 degrees each
+        for _ in 0..3 {
+            app.update();
+        }
+        let rotation = app.world.get::<Transform>(target).unwrap().rotation;
+        assert!((rotation.angle_between(Quat::IDENTITY) - 18_f32.to_radians()).abs() < 1e-4);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let forward = app.world.get::<Transform>(target).unwrap().forward();
+        assert!(forward.abs_diff_eq(Vec3::X, 1e-4));
+
+        let aim = app.world.get::<CameraAim>(target).unwrap();
+        assert!(!aim.active);
+        assert!((aim.pitch - 0.3).abs() < 1e-4);
+        // the camera orbits the focus, so its view passes through it
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        let expected = focus + Vec3::new(0.3_f32.cos(), 0.3_f32.sin(), 0.0) * 10.0;
+        assert!(aim.point.abs_diff_eq(expected, 1e-3));
+    }
+
+    #[test]
+    fn movement_policy_ignores_the_platforms_movement() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins((TransformPlugin, HierarchyPlugin));
+        // a platform turned a quarter turn, moving and turning under the target
+        let platform = app
+            .world
+            .spawn(SpatialBundle::from_transform(Transform::from_rotation(
+                Quat::from_rotation_y(FRAC_PI_2),
+            )))
+            .id();
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, SpatialBundle::default()))
+            .set_parent(platform)
+            .id();
+        app.world.spawn((
+            ThirdPersonCamera {
+                facing: TargetFacing {
+                    policy: FacingPolicy::Movement,
+                    turn_rate: 3600.0,
+                    ..default()
+                },
+                ..default()
+            },
+            Transform::default(),
+        ));
+
+        // standing still on the platform
+        for _ in 0..10 {
+            let mut platform = app.world.get_mut::<Transform>(platform).unwrap();
+            platform.translation.x += 1.0;
+            platform.rotate_y(0.1);
+            app.update();
+        }
+        let rotation = app.world.get::<Transform>(target).unwrap().rotation;
+        assert!(rotation.abs_diff_eq(Quat::IDENTITY, 1e-6));
+    }
+
+    #[test]
+    fn movement_policy_follows_a_parented_target() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins((TransformPlugin, HierarchyPlugin));
+        // a platform turned a quarter turn, carried along the x axis
+        let platform = app
+            .world
+            .spawn(SpatialBundle::from_transform(Transform::from_rotation(
+                Quat::from_rotation_y(FRAC_PI_2),
+            )))
+            .id();
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, SpatialBundle::default()))
+            .set_parent(platform)
+            .id();
+        app.world.spawn((
+            ThirdPersonCamera {
+                facing: TargetFacing {
+                    policy: FacingPolicy::Movement,
+                    turn_rate: 3600.0,
+                    ..default()
+                },
+                ..default()
+            },
+            Transform::default(),
+        ));
+
+        // walking along the platform's -x, which the quarter turn points along world +z
+        for _ in 0..10 {
+            app.world
+                .get_mut::<Transform>(platform)
+                .unwrap()
+                .translation
+                .x += 1.0;
+            app.world
+                .get_mut::<Transform>(target)
+                .unwrap()
+                .translation
+                .x -= 0.1;
+            app.update();
+        }
+        let global = app.world.get::<GlobalTransform>(target).unwrap();
+        assert!(global.forward().abs_diff_eq(Vec3::Z, 1e-4));
+    }
+
+    #[test]
+    fn aim_point_stays_on_the_view_while_orbiting() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, SpatialBundle::default()))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    aim_enabled: true,
+                    offset_enabled: true,
+                    ..default()
+                },
+                Transform::default(),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let button = app.world.get::<ThirdPersonCamera>(cam).unwrap().aim_button;
+        app.world.resource_mut::<Input<MouseButton>>().press(button);
+        app.update();
+
+        // turning quickly while aiming, the aim point has to follow the view every frame
+        for _ in 0..10 {
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(200.0, 0.0),
+            });
+            app.update();
+            let global = *app.world.get::<GlobalTransform>(cam).unwrap();
+            let aim = app.world.get::<CameraAim>(target).unwrap();
+            assert!(aim.active);
+            let to_aim = (aim.point - global.translation()).normalize();
+            assert!(global.forward().angle_between(to_aim) < 1e-3);
+        }
+    }
+}
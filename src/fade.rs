@@ -0,0 +1,372 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    ground::clamp_to_ground, ThirdPersonCamera, ThirdPersonCameraSet, ThirdPersonCameraTarget,
+};
+
+pub struct FadePlugin;
+
+impl Plugin for FadePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                fade_target.run_if(target_fade_condition),
+                unfade_target.run_if(not(target_fade_condition)),
+            )
+                .after(clamp_to_ground)
+                .in_set(ThirdPersonCameraSet),
+        );
+    }
+}
+
+/// Fades the [`ThirdPersonCameraTarget`] out as the camera gets close to the focus, for example
+/// when aiming or when the [`CameraFocusModifier`](crate::CameraFocusModifier) pulls the camera in
+///
+/// The target's [`StandardMaterial`]s are faded, unless some of its meshes are tagged with
+/// [`TargetFadeHidden`], in which case only those are hidden. Original materials are restored once
+/// the camera moves away again, or once `target_fade` is set to `None`
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{TargetFade, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             target_fade: Some(TargetFade {
+///                 start_distance: 1.2,
+///                 end_distance: 0.5,
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, Reflect)]
+pub struct TargetFade {
+    /// Camera to focus distance at which the target starts to fade
+    pub start_distance: f32,
+    /// Camera to focus distance at which the target is fully faded out
+    pub end_distance: f32,
+}
+
+impl Default for TargetFade {
+    fn default() -> Self {
+        Self {
+            start_distance: 1.2,
+            end_distance: 0.5,
+        }
+    }
+}
+
+/// Marks meshes that are hidden when the camera gets close, instead of fading the target's
+/// materials
+#[derive(Component)]
+pub struct TargetFadeHidden;
+
+// the visibility an entity had before being hidden by the target fade, restored afterwards
+#[derive(Component)]
+struct HiddenWhenClose(Visibility);
+
+// the material an entity had before it was faded, restored once it is fully opaque again
+#[derive(Component)]
+struct FadedMaterial {
+    original: Handle<StandardMaterial>,
+}
+
+// fades entities' `StandardMaterial`s. The first time an entity is faded its material is
+// swapped for a copy, so other entities sharing the material aren't affected
+#[derive(SystemParam)]
+pub(crate) struct MaterialFader<'w, 's> {
+    commands: Commands<'w, 's>,
+    material_q: Query<
+        'w,
+        's,
+        (
+            &'static mut Handle<StandardMaterial>,
+            Option<&'static FadedMaterial>,
+        ),
+    >,
+    materials: Option<ResMut<'w, Assets<StandardMaterial>>>,
+}
+
+impl MaterialFader<'_, '_> {
+    // fades the entity's material to `alpha` times the original material's alpha. An alpha of 1
+    // restores the original material
+    pub(crate) fn fade(&mut self, entity: Entity, alpha: f32) {
+        let Some(materials) = self.materials.as_mut() else {
+            return;
+        };
+        let Ok((mut handle, faded)) = self.material_q.get_mut(entity) else {
+            return;
+        };
+
+        match faded {
+            Some(faded) if alpha >= 1.0 => {
+                // the faded copy is dropped along with its last handle
+                *handle = faded.original.clone();
+                self.commands.entity(entity).remove::<FadedMaterial>();
+            }
+            Some(faded) => {
+                let Some(original) = materials.get(&faded.original) else {
+                    return;
+                };
+                let new_alpha = original.base_color.a() * alpha;
+                let current_alpha = materials.get(handle.id()).map(|m| m.base_color.a());
+                if current_alpha.is_some_and(|current| current != new_alpha) {
+                    if let Some(material) = materials.get_mut(handle.id()) {
+                        material.base_color.set_a(new_alpha);
+                    }
+                }
+            }
+            None if alpha < 1.0 => {
+                let Some(original) = materials.get(handle.id()) else {
+                    return;
+                };
+                let mut material = original.clone();
+                material.base_color.set_a(original.base_color.a() * alpha);
+                material.alpha_mode = AlphaMode::Blend;
+                let original = std::mem::replace(&mut *handle, materials.add(material));
+                self.commands
+                    .entity(entity)
+                    .insert(FadedMaterial { original });
+            }
+            None => {}
+        }
+    }
+}
+
+// only run the target fade if `target_fade` is set
+fn target_fade_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.target_fade.is_some()
+}
+
+// fades copies of the target's materials, leaving other users of the same material untouched
+fn fade_target(
+    mut commands: Commands,
+    cam_q: Query<(&ThirdPersonCamera, &Transform)>,
+    target_q: Query<Entity, With<ThirdPersonCameraTarget>>,
+    children_q: Query<&Children>,
+    mut tagged_q: Query<
+        (Entity, &mut Visibility, Option<&HiddenWhenClose>),
+        With<TargetFadeHidden>,
+    >,
+    mut fader: MaterialFader,
+) {
+    let Ok((cam, cam_transform)) = cam_q.get_single() else {
+        return;
+    };
+    let Some(fade) = cam.target_fade else {
+        return;
+    };
+    let Ok(target) = target_q.get_single() else {
+        return;
+    };
+
+    // 1 when at or beyond the start distance, 0 when at or within the end distance
+    let distance = cam_transform.translation.distance(cam.focus);
+    let alpha = ((distance - fade.end_distance)
+        / (fade.start_distance - fade.end_distance).max(f32::EPSILON))
+    .clamp(0.0, 1.0);
+
+    if !tagged_q.is_empty() {
+        let hide = alpha < 0.5;
+        for (entity, mut visibility, hidden) in tagged_q.iter_mut() {
+            match (hide, hidden) {
+                (true, None) => {
+                    commands.entity(entity).insert(HiddenWhenClose(*visibility));
+                    *visibility = Visibility::Hidden;
+                }
+                (false, Some(HiddenWhenClose(original))) => {
+                    commands.entity(entity).remove::<HiddenWhenClose>();
+                    *visibility = *original;
+                }
+                _ => {}
+            }
+        }
+        return;
+    }
+
+    for entity in std::iter::once(target).chain(children_q.iter_descendants(target)) {
+        fader.fade(entity, alpha);
+    }
+}
+
+// puts the target's original materials and visibility back once `target_fade` is unset
+fn unfade_target(
+    mut commands: Commands,
+    target_q: Query<Entity, With<ThirdPersonCameraTarget>>,
+    children_q: Query<&Children>,
+    faded_q: Query<(), With<FadedMaterial>>,
+    mut hidden_q: Query<(Entity, &mut Visibility, &HiddenWhenClose)>,
+    mut fader: MaterialFader,
+) {
+    for (entity, mut visibility, HiddenWhenClose(original)) in hidden_q.iter_mut() {
+        commands.entity(entity).remove::<HiddenWhenClose>();
+        *visibility = *original;
+    }
+
+    // faded occluders are left to the occluder fade
+    if faded_q.is_empty() {
+        return;
+    }
+    let Ok(target) = target_q.get_single() else {
+        return;
+    };
+    for entity in std::iter::once(target).chain(children_q.iter_descendants(target)) {
+        if faded_q.contains(entity) {
+            fader.fade(entity, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, TargetFade, TargetFadeHidden, ThirdPersonCamera, ThirdPersonCameraTarget, Zoom,
+    };
+
+    #[test]
+    fn fades_a_copy_of_the_material_when_close() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<StandardMaterial>();
+        let shared = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, Transform::default()))
+            .id();
+        let mesh = app.world.spawn(shared.clone()).set_parent(target).id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    target_fade: Some(TargetFade::default()),
+                    zoom: Zoom::new(0.3, 0.3),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        let alpha = |app: &App, handle: &Handle<StandardMaterial>| {
+            let materials = app.world.resource::<Assets<StandardMaterial>>();
+            materials.get(handle).unwrap().base_color.a()
+        };
+
+        app.update();
+        app.update();
+        let faded = app
+            .world
+            .get::<Handle<StandardMaterial>>(mesh)
+            .unwrap()
+            .clone();
+        assert_ne!(faded, shared);
+        assert_eq!(alpha(&app, &faded), 0.0);
+        assert_eq!(alpha(&app, &shared), 1.0);
+
+        // zooming out puts the shared material back
+        app.world.get_mut::<ThirdPersonCamera>(cam).unwrap().zoom = Zoom::new(2.0, 2.0);
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(mesh),
+            Some(&shared)
+        );
+    }
+
+    #[test]
+    fn turning_the_fade_off_restores_the_target() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<StandardMaterial>();
+        let shared = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, Transform::default()))
+            .id();
+        let mesh = app.world.spawn(shared.clone()).set_parent(target).id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    target_fade: Some(TargetFade::default()),
+                    zoom: Zoom::new(0.3, 0.3),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        app.update();
+        assert_ne!(
+            app.world.get::<Handle<StandardMaterial>>(mesh),
+            Some(&shared)
+        );
+
+        // still zoomed in, but the fade is off
+        app.world
+            .get_mut::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .target_fade = None;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(mesh),
+            Some(&shared)
+        );
+    }
+
+    #[test]
+    fn turning_the_fade_off_shows_hidden_meshes() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((ThirdPersonCameraTarget, Transform::default()))
+            .id();
+        let hair = app
+            .world
+            .spawn((TargetFadeHidden, Visibility::Inherited))
+            .set_parent(target)
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    target_fade: Some(TargetFade::default()),
+                    zoom: Zoom::new(0.3, 0.3),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<Visibility>(hair), Some(&Visibility::Hidden));
+
+        app.world
+            .get_mut::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .target_fade = None;
+        app.update();
+        assert_eq!(
+            app.world.get::<Visibility>(hair),
+            Some(&Visibility::Inherited)
+        );
+    }
+}
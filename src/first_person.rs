@@ -0,0 +1,339 @@
+use bevy::prelude::*;
+
+use crate::{position_camera, ThirdPersonCamera, ThirdPersonCameraSet, ThirdPersonCameraTarget};
+
+pub struct FirstPersonPlugin;
+
+impl Plugin for FirstPersonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                (first_person, hide_target)
+                    .chain()
+                    .run_if(first_person_condition),
+                show_target.run_if(not(first_person_condition)),
+            )
+                .after(position_camera)
+                .in_set(ThirdPersonCameraSet),
+        );
+    }
+}
+
+/// Zooming in past [`Zoom::min`](crate::Zoom) switches to a first person view from the target's
+/// eyes, zooming back out returns to third person. The camera keeps its rotation, pitch limits and
+/// sensitivity, and blends between the two positions over `transition_time`
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{FirstPerson, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             first_person: Some(FirstPerson::new(Vec3::new(0.0, 1.6, 0.0))),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct FirstPerson {
+    /// Position of the eyes relative to the target, used when there is no [`FirstPersonEye`]
+    pub eye_offset: Vec3,
+    /// Seconds it takes to blend between third and first person
+    pub transition_time: f32,
+    active: bool,
+    blend: f32,
+}
+
+impl FirstPerson {
+    pub fn new(eye_offset: Vec3) -> Self {
+        Self {
+            eye_offset,
+            transition_time: 0.25,
+            active: false,
+            blend: 0.0,
+        }
+    }
+
+    /// True once zoomed in past the minimum zoom, including while blending into first person
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // zooming in at the minimum zoom enters first person and zooming out leaves it. Returns true
+    // when the zoom was used up by first person rather than changing the radius
+    pub(crate) fn zoom(&mut self, lines: f32, at_min: bool) -> bool {
+        if self.active {
+            if lines < 0.0 {
+                self.active = false;
+            }
+            return true;
+        }
+        if lines > 0.0 && at_min {
+            self.active = true;
+            return true;
+        }
+        false
+    }
+}
+
+impl Default for FirstPerson {
+    fn default() -> Self {
+        Self::new(Vec3::new(0.0, 1.6, 0.0))
+    }
+}
+
+/// Marks the entity, typically a child of the [`ThirdPersonCameraTarget`] such as a head bone,
+/// whose position is used as the eyes in first person
+#[derive(Component)]
+pub struct FirstPersonEye;
+
+/// Marks meshes that are hidden in first person. When no entity has this component, the whole
+/// [`ThirdPersonCameraTarget`] is hidden instead
+#[derive(Component)]
+pub struct FirstPersonHidden;
+
+// the visibility an entity had before being hidden in first person, restored when leaving it
+#[derive(Component)]
+struct HiddenInFirstPerson(Visibility);
+
+// only run first person logic if `first_person` is set
+fn first_person_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.first_person.is_some()
+}
+
+// blends the camera between its third person position and the target's eyes
+pub(crate) fn first_person(
+    mut cam_q: Query<(&mut ThirdPersonCamera, &mut Transform), Without<ThirdPersonCameraTarget>>,
+    target_q: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+    eye_q: Query<&GlobalTransform, With<FirstPersonEye>>,
+    time: Res<Time>,
+) {
+    let Ok((mut cam, mut cam_transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+    let Some(fp) = cam.first_person.as_mut() else {
+        return;
+    };
+
+    let step = time.delta_seconds() / fp.transition_time.max(f32::EPSILON);
+    fp.blend = if fp.active {
+        (fp.blend + step).min(1.0)
+    } else {
+        (fp.blend - step).max(0.0)
+    };
+
+    if fp.blend <= 0.0 {
+        return;
+    }
+
+    let eye = match (eye_q.get_single(), target_q.get_single()) {
+        (Ok(eye), _) => eye.translation(),
+        (_, Ok(target)) => target.transform_point(fp.eye_offset),
+        _ => return,
+    };
+
+    // ease in and out so the camera doesn't start or stop moving abruptly
+    let t = fp.blend * fp.blend * (3.0 - 2.0 * fp.blend);
+    cam_transform.translation = cam_transform.translation.lerp(eye, t);
+}
+
+// hides the target once the camera is halfway to its eyes and shows it again on the way out
+fn hide_target(
+    mut commands: Commands,
+    cam_q: Query<&ThirdPersonCamera>,
+    tagged_q: Query<Entity, With<FirstPersonHidden>>,
+    target_q: Query<Entity, With<ThirdPersonCameraTarget>>,
+    mut visibility_q: Query<(&mut Visibility, Option<&HiddenInFirstPerson>)>,
+) {
+    let Ok(cam) = cam_q.get_single() else {
+        return;
+    };
+    let Some(fp) = &cam.first_person else {
+        return;
+    };
+
+    let hide = fp.blend > 0.5;
+    let entities = if tagged_q.is_empty() {
+        target_q.iter().collect::<Vec<_>>()
+    } else {
+        tagged_q.iter().collect()
+    };
+
+    for entity in entities {
+        let Ok((mut visibility, hidden)) = visibility_q.get_mut(entity) else {
+            continue;
+        };
+        match (hide, hidden) {
+            (true, None) => {
+                commands
+                    .entity(entity)
+                    .insert(HiddenInFirstPerson(*visibility));
+                *visibility = Visibility::Hidden;
+            }
+            (false, Some(HiddenInFirstPerson(original))) => {
+                commands.entity(entity).remove::<HiddenInFirstPerson>();
+                *visibility = *original;
+            }
+            _ => {}
+        }
+    }
+}
+
+// shows anything still hidden once `first_person` is turned off
+fn show_target(
+    mut commands: Commands,
+    mut hidden_q: Query<(Entity, &mut Visibility, &HiddenInFirstPerson)>,
+) {
+    for (entity, mut visibility, HiddenInFirstPerson(original)) in hidden_q.iter_mut() {
+        commands.entity(entity).remove::<HiddenInFirstPerson>();
+        *visibility = *original;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        input::mouse::{MouseScrollUnit, MouseWheel},
+        prelude::*,
+    };
+
+    use crate::{test_app, FirstPerson, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+    // scrolls by `y` lines, then lets the transition finish
+    fn scroll(app: &mut App, y: f32) {
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y,
+            window: Entity::PLACEHOLDER,
+        });
+        for _ in 0..10 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn scrolling_past_min_enters_and_leaves_first_person() {
+        let mut app = test_app(Duration::from_millis(50));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        app.world.entity_mut(target).insert(Visibility::Visible);
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    first_person: Some(FirstPerson::new(Vec3::new(0.0, 1.6, 0.0))),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        // zoom all the way in, then once more to enter first person
+        scroll(&mut app, 20.0);
+        scroll(&mut app, 1.0);
+        let eye = app.world.get::<Transform>(cam).unwrap().translation;
+        assert!(eye.abs_diff_eq(Vec3::new(0.0, 1.6, 0.0), 1e-4));
+        assert_eq!(
+            app.world.get::<Visibility>(target),
+            Some(&Visibility::Hidden)
+        );
+
+        scroll(&mut app, -1.0);
+        let position = app.world.get::<Transform>(cam).unwrap().translation;
+        assert!(position.distance(Vec3::new(0.0, 1.6, 0.0)) > 1.0);
+        assert_eq!(
+            app.world.get::<Visibility>(target),
+            Some(&Visibility::Visible)
+        );
+    }
+
+    #[test]
+    fn aiming_below_min_zoom_does_not_enter_first_person() {
+        let mut app = test_app(Duration::from_millis(50));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            GlobalTransform::from_translation(Vec3::ZERO),
+        ));
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    aim_enabled: true,
+                    first_person: Some(FirstPerson::default()),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        let button = app.world.get::<ThirdPersonCamera>(cam).unwrap().aim_button;
+        app.world.resource_mut::<Input<MouseButton>>().press(button);
+        for _ in 0..20 {
+            app.update();
+        }
+
+        scroll(&mut app, 1.0);
+        let first_person = app
+            .world
+            .get::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .first_person
+            .as_ref()
+            .unwrap();
+        assert!(!first_person.is_active());
+    }
+
+    #[test]
+    fn turning_first_person_off_shows_the_target() {
+        let mut app = test_app(Duration::from_millis(50));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        app.world.entity_mut(target).insert(Visibility::Visible);
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    first_person: Some(FirstPerson::default()),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        scroll(&mut app, 20.0);
+        scroll(&mut app, 1.0);
+        assert_eq!(
+            app.world.get::<Visibility>(target),
+            Some(&Visibility::Hidden)
+        );
+
+        app.world
+            .get_mut::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .first_person = None;
+        app.update();
+        assert_eq!(
+            app.world.get::<Visibility>(target),
+            Some(&Visibility::Visible)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use bevy::{math::Rect, prelude::*};
+
+use crate::{effective_radius, smooth, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+/// Lets the focus move around the screen instead of staying in its center. While the focus is
+/// inside `dead_zone` the camera doesn't move, inside `soft_zone` the camera catches up until the
+/// focus is back in the dead zone, and the focus never leaves the soft zone. Zones are rectangles
+/// in normalized viewport coordinates, from (0, 0) at the top left to (1, 1) at the bottom right,
+/// measured as if the focus was centered, so the shoulder offset moves them along with the view.
+/// Depth is always followed
+///
+/// # Examples
+///
+/// ```
+/// use bevy::{math::Rect, prelude::*};
+/// use bevy_third_person_camera::{FramingComposer, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     let mut composer = FramingComposer::new(
+///         Rect::new(0.45, 0.4, 0.55, 0.6),
+///         Rect::new(0.2, 0.2, 0.8, 0.8),
+///     );
+///     composer.damping = 2.0;
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             composer: Some(composer),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct FramingComposer {
+    /// Area of the screen where the focus can move without moving the camera
+    pub dead_zone: Rect,
+    /// Area of the screen where the camera catches up with the focus. Should contain `dead_zone`
+    pub soft_zone: Rect,
+    /// How quickly the camera catches up inside the soft zone. Higher values are snappier
+    pub damping: f32,
+    // the point the camera is centered on
+    focus: Option<Vec3>,
+}
+
+impl FramingComposer {
+    pub fn new(dead_zone: Rect, soft_zone: Rect) -> Self {
+        Self {
+            dead_zone,
+            soft_zone,
+            damping: 4.0,
+            focus: None,
+        }
+    }
+}
+
+impl Default for FramingComposer {
+    fn default() -> Self {
+        Self::new(
+            Rect::new(0.45, 0.4, 0.55, 0.6),
+            Rect::new(0.2, 0.2, 0.8, 0.8),
+        )
+    }
+}
+
+// only run framing logic if `composer` is set
+pub(crate) fn composer_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.composer.is_some()
+}
+
+// moves the focus the camera is centered on only as far as needed to keep the desired focus in
+// the dead zone, damped inside the soft zone
+pub(crate) fn compose(
+    mut cam_q: Query<
+        (&mut ThirdPersonCamera, &Transform, Option<&Projection>),
+        Without<ThirdPersonCameraTarget>,
+    >,
+    time: Res<Time>,
+) {
+    let Ok((mut cam, transform, projection)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    let desired = cam.focus;
+    let radius = effective_radius(&cam, transform.rotation);
+    let (fov, aspect_ratio) = match projection {
+        Some(Projection::Perspective(perspective)) => (perspective.fov, perspective.aspect_ratio),
+        _ => {
+            let perspective = PerspectiveProjection::default();
+            (perspective.fov, perspective.aspect_ratio)
+        }
+    };
+    // half the size of the view, in world units per unit of depth
+    let half_view = Vec2::new((fov / 2.0).tan() * aspect_ratio, (fov / 2.0).tan());
+
+    let composer = cam.composer.as_mut().unwrap();
+    let focus = *composer.focus.get_or_insert(desired);
+
+    // where the desired focus is in view space, with the camera centered on the current focus
+    let delta = transform.rotation.inverse() * (desired - focus);
+    let depth = radius - delta.z;
+    if depth <= f32::EPSILON || half_view.min_element() <= f32::EPSILON {
+        composer.focus = Some(desired);
+        return;
+    }
+    // normalized viewport coordinates of the desired focus, with y pointing down
+    let to_screen =
+        |view: Vec2| Vec2::new(0.5, 0.5) + view / depth / half_view * Vec2::new(0.5, -0.5);
+    let to_view =
+        |screen: Vec2| (screen - Vec2::new(0.5, 0.5)) / Vec2::new(0.5, -0.5) * half_view * depth;
+    let screen = to_screen(delta.truncate());
+
+    // never let the focus leave the soft zone, then catch up towards the dead zone
+    let soft = screen.clamp(composer.soft_zone.min, composer.soft_zone.max);
+    let dead = soft.clamp(composer.dead_zone.min, composer.dead_zone.max);
+    let new_screen = smooth(soft, dead, composer.damping, time.delta_seconds());
+
+    // move the focus so the desired focus lands on its new place on screen, following depth fully
+    let shift = if new_screen == screen {
+        Vec2::ZERO
+    } else {
+        delta.truncate() - to_view(new_screen)
+    };
+    let new_focus = focus + transform.rotation * shift.extend(delta.z);
+    composer.focus = Some(new_focus);
+    cam.focus = new_focus;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_4, time::Duration};
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, CameraRig, CameraRigs, FramingComposer, ThirdPersonCamera,
+        ThirdPersonCameraTarget, Zoom,
+    };
+
+    #[test]
+    fn focus_stays_still_in_the_dead_zone_and_catches_up_in_the_soft_zone() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    composer: Some(FramingComposer::default()),
+                    zoom: Zoom::new(2.0, 2.0),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world
+            .entity_mut(cam)
+            .insert(Projection::Perspective(default()));
+        let focus_after_moving = |app: &mut App, x: f32| {
+            *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+                GlobalTransform::from_translation(Vec3::new(x, 0.0, 0.0));
+            app.update();
+            app.world.get::<ThirdPersonCamera>(cam).unwrap().focus
+        };
+        app.update();
+
+        // small movements inside the dead zone don't move the camera
+        assert_eq!(focus_after_moving(&mut app, 0.05).x, 0.0);
+
+        // a large movement is caught inside the soft zone straight away
+        let half_height = (FRAC_PI_4 / 2.0).tan() * 2.0;
+        let focus = focus_after_moving(&mut app, 1.0);
+        assert!(1.0 - focus.x <= 0.6 * half_height + 1e-3);
+        assert!(1.0 - focus.x > 0.1 * half_height);
+
+        // and then settles with the target at the edge of the dead zone
+        for _ in 0..30 {
+            focus_after_moving(&mut app, 1.0);
+        }
+        let focus = focus_after_moving(&mut app, 1.0);
+        assert!((1.0 - focus.x - 0.1 * half_height).abs() < 1e-3);
+    }
+
+    #[test]
+    fn zones_are_measured_at_the_rig_radius() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        // level with the target, the rig doubles the zoom radius to 4
+        let rigs = CameraRigs {
+            middle: CameraRig {
+                radius: 2.0,
+                ..default()
+            },
+            ..default()
+        };
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    composer: Some(FramingComposer::default()),
+                    rigs: Some(rigs),
+                    zoom: Zoom::new(2.0, 2.0),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world
+            .entity_mut(cam)
+            .insert(Projection::Perspective(default()));
+        app.update();
+
+        for _ in 0..40 {
+            *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+                GlobalTransform::from_translation(Vec3::X);
+            app.update();
+        }
+        // the target settles at the edge of the dead zone as seen from 4 units away
+        let half_height = (FRAC_PI_4 / 2.0).tan() * 4.0;
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!((1.0 - focus.x - 0.1 * half_height).abs() < 1e-3);
+        let translation = app.world.get::<Transform>(cam).unwrap().translation;
+        assert!((translation.z - 4.0).abs() < 1e-4);
+    }
+}
@@ -1,11 +1,10 @@
-use std::f32::consts::PI;
-
-use crate::{GamepadResource, ThirdPersonCamera};
-use bevy::{
-    input::gamepad::{GamepadConnection::*, *},
-    prelude::*,
-    window::PrimaryWindow,
+use crate::{
+    orbit, window::CameraWindows, zoom, AimAssistTarget, GamepadResource, ThirdPersonCamera,
 };
+use bevy::prelude::*;
+
+// lines of scrolling a held zoom button is worth per second
+const ZOOM_LINES_PER_SECOND: f32 = 6.0;
 
 pub struct GamePadPlugin;
 
@@ -13,40 +12,19 @@ impl Plugin for GamePadPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (
-                connections,
-                (orbit_gamepad, zoom_gamepad).run_if(resource_exists::<GamepadResource>()),
-            ),
+            (orbit_gamepad, zoom_gamepad).run_if(resource_exists::<GamepadResource>()),
         );
     }
 }
 
-fn connections(
-    mut cmds: Commands,
-    gamepad_res: Option<Res<GamepadResource>>,
-    mut gamepad_evr: EventReader<GamepadConnectionEvent>,
-) {
-    for ev in gamepad_evr.read() {
-        match &ev.connection {
-            Connected(_info) => {
-                // if no gamepad is setup yet, use this one
-                if gamepad_res.is_none() {
-                    cmds.insert_resource(GamepadResource(Gamepad::new(0)));
-                }
-                // println!("Gamepad connected");
-            }
-            Disconnected => {
-                cmds.remove_resource::<GamepadResource>();
-                // println!("Gamepad disconnected");
-            }
-        }
-    }
-}
-
+// zooms at a constant rate per second while a zoom button is held, so together with
+// `orbit_gamepad` the same stick and button input gives the same pose at any frame rate
 pub fn zoom_gamepad(
     btns: Res<Input<GamepadButton>>,
     gamepad_res: Option<Res<GamepadResource>>,
-    mut cam_q: Query<&mut ThirdPersonCamera, With<ThirdPersonCamera>>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, Option<&Camera>)>,
+    time: Res<Time>,
+    windows: CameraWindows,
 ) {
     let gamepad = if let Some(gp) = gamepad_res {
         gp.0
@@ -54,32 +32,41 @@ pub fn zoom_gamepad(
         return;
     };
 
-    if let Ok(mut cam) = cam_q.get_single_mut() {
+    if let Ok((mut cam, camera)) = cam_q.get_single_mut() {
+        if !windows.takes_input(camera) {
+            return;
+        }
+
         let gp = &cam.gamepad_settings;
 
         let zoom_out = GamepadButton::new(gamepad, gp.zoom_out_button.button_type);
         let zoom_in = GamepadButton::new(gamepad, gp.zoom_in_button.button_type);
 
-        let mut new_radius = cam.zoom.radius;
+        let lines = ZOOM_LINES_PER_SECOND * time.delta_seconds();
 
         // zoom out
         if btns.pressed(zoom_out) {
-            new_radius += cam.zoom.radius * 0.01;
-            cam.zoom.radius = new_radius.clamp(cam.zoom.min, cam.zoom.max);
+            zoom(&mut cam, -lines);
         // zoom in
         } else if btns.pressed(zoom_in) {
-            new_radius -= cam.zoom.radius * 0.01;
-            cam.zoom.radius = new_radius.clamp(cam.zoom.min, cam.zoom.max);
+            zoom(&mut cam, lines);
         }
     }
 }
 
+// stick deflection is turned into degrees per second, so holding the stick turns the camera at
+// the same speed regardless of frame rate or window size
+//
+// While aiming, `AimAssist` slows the camera down over targets and pulls it
+// towards them
 pub fn orbit_gamepad(
-    window_q: Query<&Window, With<PrimaryWindow>>,
-    mut cam_q: Query<(&ThirdPersonCamera, &mut Transform), With<ThirdPersonCamera>>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, Option<&Camera>, &mut Transform)>,
+    target_q: Query<(&GlobalTransform, &AimAssistTarget)>,
     btns: Res<Input<GamepadButton>>,
     axis: Res<Axis<GamepadAxis>>,
     gamepad_res: Option<Res<GamepadResource>>,
+    time: Res<Time>,
+    windows: CameraWindows,
 ) {
     // return gamepad if one is connected
     let gamepad = if let Some(gp) = gamepad_res {
@@ -88,54 +75,213 @@ pub fn orbit_gamepad(
         return;
     };
 
-    let Ok((cam, mut cam_transform)) = cam_q.get_single_mut() else {
+    let Ok((mut cam, camera, mut cam_transform)) = cam_q.get_single_mut() else {
         return;
     };
 
-    if cam.mouse_orbit_button_enabled && !btns.pressed(cam.gamepad_settings.mouse_orbit_button) {
+    let orbit_button =
+        GamepadButton::new(gamepad, cam.gamepad_settings.mouse_orbit_button.button_type);
+    if cam.mouse_orbit_button_enabled && !btns.pressed(orbit_button) {
         return;
     }
 
     let x_axis = GamepadAxis::new(gamepad, GamepadAxisType::RightStickX);
     let y_axis = GamepadAxis::new(gamepad, GamepadAxisType::RightStickY);
 
-    let deadzone = 0.5;
     let mut rotation = Vec2::ZERO;
     if let (Some(x), Some(y)) = (axis.get(x_axis), axis.get(y_axis)) {
-        if x.abs() > deadzone || y.abs() > deadzone {
-            rotation = Vec2::new(x, y);
-        }
+        let settings = &cam.gamepad_settings;
+        rotation = settings
+            .invert
+            .apply(settings.orbit_stick.apply(Vec2::new(x, y)));
+    }
+
+    // gamepads aren't tied to a window, only orbit while the camera's window is focused
+    if !windows.takes_input(camera) {
+        rotation = Vec2::ZERO;
     }
 
     if rotation.length_squared() > 0.0 {
-        let window = window_q.get_single().unwrap();
-        let delta_x = {
-            let delta = rotation.x / window.width()
-                * std::f32::consts::PI
-                * 2.0
-                * cam.gamepad_settings.x_sensitivity;
-            delta
-        };
-        let delta_y = -rotation.y / window.height() * PI * cam.gamepad_settings.y_sensitivity;
-        let yaw = Quat::from_rotation_y(-delta_x);
-        let pitch = Quat::from_rotation_x(-delta_y);
-        cam_transform.rotation = yaw * cam_transform.rotation; // rotate around global y axis
-
-        let new_rotation = cam_transform.rotation * pitch;
-
-        // check if new rotation will cause camera to go beyond the 180 degree vertical bounds
-        let up_vector = new_rotation * Vec3::Y;
-        if up_vector.y > 0.0 {
-            cam_transform.rotation = new_rotation;
+        let settings = &cam.gamepad_settings;
+        let delta_x = rotation.x * settings.x_sensitivity.to_radians() * time.delta_seconds();
+        let delta_y = rotation.y * settings.y_sensitivity.to_radians() * time.delta_seconds();
+        let mut turn = Vec2::new(-delta_x, delta_y);
+
+        if let (Some(assist), true) = (settings.aim_assist, cam.aim_state.is_active()) {
+            let targets = target_q
+                .iter()
+                .map(|(transform, target)| (transform.translation(), target.radius));
+            turn = assist.apply(
+                &cam_transform,
+                cam.up.current(),
+                targets,
+                turn,
+                rotation.length(),
+                time.delta_seconds(),
+            );
+        }
+        orbit(
+            &mut cam_transform.rotation,
+            cam.up.current(),
+            turn.x,
+            turn.y,
+        );
+        if let Some(chase) = cam.chase.as_mut() {
+            chase.free_look();
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, AimAssist, AimAssistTarget, CustomGamepadSettings, GamepadResource,
+        ThirdPersonCamera, Zoom,
+    };
 
-    let rot_matrix = Mat3::from_quat(cam_transform.rotation);
-    // apply the offset if offset_enabled is true
-    let mut offset = Vec3::ZERO;
-    if cam.offset_enabled {
-        offset = rot_matrix.mul_vec3(Vec3::new(cam.offset.offset.0, cam.offset.offset.1, 0.0));
+    // holds zoom in and the right stick up and to the right for half a second
+    fn pose_after_holding_stick(fps: u32) -> Transform {
+        let gamepad = Gamepad::new(0);
+        let mut app = test_app(Duration::from_secs_f32(1.0 / fps as f32));
+        app.insert_resource(GamepadResource(gamepad));
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    zoom: Zoom::new(0.5, 10.0),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        // time does not advance on the first update
+        app.update();
+
+        let x_axis = GamepadAxis::new(gamepad, GamepadAxisType::RightStickX);
+        let y_axis = GamepadAxis::new(gamepad, GamepadAxisType::RightStickY);
+        let zoom_in = GamepadButton::new(gamepad, GamepadButtonType::DPadUp);
+        app.world
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(x_axis, 0.8);
+        app.world
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(y_axis, 0.7);
+        app.world
+            .resource_mut::<Input<GamepadButton>>()
+            .press(zoom_in);
+        for _ in 0..fps / 2 {
+            app.update();
+        }
+
+        // the camera is placed at the new radius on the frame after zooming
+        app.world
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(x_axis, 0.0);
+        app.world
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(y_axis, 0.0);
+        app.world
+            .resource_mut::<Input<GamepadButton>>()
+            .release(zoom_in);
+        app.update();
+        *app.world.get::<Transform>(cam).unwrap()
+    }
+
+    #[test]
+    fn orbit_and_zoom_are_frame_rate_independent() {
+        let (slow, fast) = (pose_after_holding_stick(30), pose_after_holding_stick(240));
+        assert!(slow.rotation.abs_diff_eq(fast.rotation, 1e-4));
+        assert!(slow.translation.abs_diff_eq(fast.translation, 1e-4));
+    }
+
+    // aims and holds the right stick for 0.3 seconds, returning where the camera looks
+    fn forward_after_aiming(assist: Option<AimAssist>, stick: Vec2, target: Vec3) -> Vec3 {
+        forward_after_aiming_with_offset(assist, stick, target, false)
+    }
+
+    fn forward_after_aiming_with_offset(
+        assist: Option<AimAssist>,
+        stick: Vec2,
+        target: Vec3,
+        offset_enabled: bool,
+    ) -> Vec3 {
+        let gamepad = Gamepad::new(0);
+        let mut app = test_app(Duration::from_millis(100));
+        app.insert_resource(GamepadResource(gamepad));
+        app.world.spawn((
+            AimAssistTarget { radius: 0.5 },
+            GlobalTransform::from_translation(target),
+        ));
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    aim_enabled: true,
+                    offset_enabled,
+                    gamepad_settings: CustomGamepadSettings {
+                        aim_assist: assist,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+
+        let aim = GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger2);
+        app.world.resource_mut::<Input<GamepadButton>>().press(aim);
+        // the shoulder offset is applied once aiming
+        app.update();
+        let mut axes = app.world.resource_mut::<Axis<GamepadAxis>>();
+        axes.set(
+            GamepadAxis::new(gamepad, GamepadAxisType::RightStickX),
+            stick.x,
+        );
+        axes.set(
+            GamepadAxis::new(gamepad, GamepadAxisType::RightStickY),
+            stick.y,
+        );
+        for _ in 0..3 {
+            app.update();
+        }
+        app.world.get::<Transform>(cam).unwrap().forward()
+    }
+
+    #[test]
+    fn aim_assist_slows_and_pulls_towards_targets() {
+        // turning right is slowed down while the reticle is over a target straight ahead
+        let ahead = Vec3::new(0.0, 0.0, -5.0);
+        let assisted = forward_after_aiming(Some(AimAssist::default()), Vec2::X, ahead);
+        let unassisted = forward_after_aiming(None, Vec2::X, ahead);
+        assert!(assisted.x > 0.0 && assisted.x < unassisted.x);
+
+        // looking up, the camera is pulled sideways towards a target slightly to the right
+        let right = Vec3::new(0.5, 0.0, -5.0);
+        assert!(forward_after_aiming(Some(AimAssist::default()), Vec2::Y, right).x > 0.0);
+        assert!(forward_after_aiming(None, Vec2::Y, right).x.abs() < 1e-6);
+    }
+
+    #[test]
+    fn aim_assist_cone_follows_the_shoulder_offset() {
+        // the reticle is over the shoulder, 0.5 right and 0.4 above the focus. A target on that
+        // ray isn't pulled towards, one straight ahead of the focus is off to the left
+        let on_reticle = Vec3::new(0.5, 0.4, -5.0);
+        let forward =
+            forward_after_aiming_with_offset(Some(AimAssist::default()), Vec2::Y, on_reticle, true);
+        assert!(forward.x.abs() < 1e-4);
+
+        let ahead_of_focus = Vec3::new(0.0, 0.0, -5.0);
+        let forward = forward_after_aiming_with_offset(
+            Some(AimAssist::default()),
+            Vec2::Y,
+            ahead_of_focus,
+            true,
+        );
+        assert!(forward.x < 0.0);
     }
-    cam_transform.translation =
-        cam.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, cam.zoom.radius)) + offset;
 }
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+
+use crate::{first_person::first_person, ThirdPersonCamera, ThirdPersonCameraSet};
+
+pub struct GroundPlugin;
+
+impl Plugin for GroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            clamp_to_ground
+                .after(first_person)
+                .in_set(ThirdPersonCameraSet)
+                .run_if(ground_clamp_condition),
+        );
+    }
+}
+
+/// Answers how high the ground is under a point, used by [`GroundClamp`] to keep the camera from
+/// dipping under the floor. Implemented for [`FlatGround`] and for closures, and can be implemented
+/// over a heightfield or terrain
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{FlatGround, GroundHeight};
+///
+/// assert_eq!(FlatGround { height: 1.0 }.height_at(Vec3::new(5.0, 3.0, -2.0)), Some(1.0));
+///
+/// // a gentle slope along the x axis
+/// let slope = |position: Vec3| Some(position.x * 0.5);
+/// assert_eq!(slope.height_at(Vec3::new(4.0, 0.0, 0.0)), Some(2.0));
+/// ```
+pub trait GroundHeight: Send + Sync + 'static {
+    /// Height of the ground under `position`, measured along the camera's up, or `None` if there
+    /// is no ground there
+    fn height_at(&self, position: Vec3) -> Option<f32>;
+}
+
+impl<F> GroundHeight for F
+where
+    F: Fn(Vec3) -> Option<f32> + Send + Sync + 'static,
+{
+    fn height_at(&self, position: Vec3) -> Option<f32> {
+        self(position)
+    }
+}
+
+/// An endless floor at `height` along the camera's up. With the up from a
+/// [`PointGravity`](crate::PointGravity) centered on the origin this is a sphere of radius `height`
+#[derive(Clone, Copy, Default)]
+pub struct FlatGround {
+    pub height: f32,
+}
+
+impl GroundHeight for FlatGround {
+    fn height_at(&self, _position: Vec3) -> Option<f32> {
+        Some(self.height)
+    }
+}
+
+/// How the camera is kept above the ground
+#[derive(Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum GroundClampMode {
+    /// Raise the camera straight up, keeping its orientation
+    #[default]
+    Raise,
+    /// Pull the camera in towards the focus until it is clear of the ground. Falls back to
+    /// [`GroundClampMode::Raise`] while the focus itself is within `clearance` of the ground
+    ShortenRadius,
+}
+
+/// Keeps the camera at least `clearance` above the ground, which is most useful with a
+/// [`CameraFocusModifier`](crate::CameraFocusModifier) and low camera angles. Heights and the
+/// clearance are measured along the camera's current [`CameraUp`](crate::CameraUp)
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{FlatGround, GroundClamp, GroundClampMode, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             ground_clamp: Some(GroundClamp {
+///                 ground: Box::new(FlatGround { height: 0.0 }),
+///                 clearance: 0.2,
+///                 mode: GroundClampMode::ShortenRadius,
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct GroundClamp {
+    /// Ignored by reflection, a `GroundClamp` built through reflection, for example cloned or
+    /// edited from an inspector, has a [`FlatGround`] at height 0 instead
+    #[reflect(ignore, default = "crate::unreflected_ground")]
+    pub ground: Box<dyn GroundHeight>,
+    /// Minimum distance kept between the camera and the ground below it
+    pub clearance: f32,
+    pub mode: GroundClampMode,
+}
+
+impl Default for GroundClamp {
+    fn default() -> Self {
+        Self {
+            ground: Box::new(FlatGround::default()),
+            clearance: 0.2,
+            mode: GroundClampMode::default(),
+        }
+    }
+}
+
+impl GroundClamp {
+    // how far the position is below the minimum clearance along `up`, 0 when it is clear of the
+    // ground
+    fn depth(&self, position: Vec3, up: Vec3) -> f32 {
+        self.ground.height_at(position).map_or(0.0, |height| {
+            (height + self.clearance - position.dot(up)).max(0.0)
+        })
+    }
+}
+
+// number of points along the camera's arm checked when shortening the radius
+const RADIUS_STEPS: u32 = 32;
+
+// only run the ground clamp if `ground_clamp` is set
+fn ground_clamp_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.ground_clamp.is_some()
+}
+
+// keeps the camera above the ground by raising it or by shortening its radius
+pub(crate) fn clamp_to_ground(mut cam_q: Query<(&ThirdPersonCamera, &mut Transform)>) {
+    let Ok((cam, mut cam_transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+    let Some(clamp) = &cam.ground_clamp else {
+        return;
+    };
+
+    let up = cam.up.current();
+    let position = cam_transform.translation;
+    let depth = clamp.depth(position, up);
+    if depth <= 0.0 {
+        return;
+    }
+
+    // no point along the arm is clear of the ground when the focus itself isn't
+    let mode = match clamp.mode {
+        GroundClampMode::ShortenRadius if clamp.depth(cam.focus, up) > 0.0 => {
+            GroundClampMode::Raise
+        }
+        mode => mode,
+    };
+
+    match mode {
+        GroundClampMode::Raise => cam_transform.translation += up * depth,
+        GroundClampMode::ShortenRadius => {
+            // walk out from the focus and stop at the last point that is clear of the ground
+            let arm = position - cam.focus;
+            let mut clear = 0.0;
+            for step in 1..=RADIUS_STEPS {
+                let t = step as f32 / RADIUS_STEPS as f32;
+                if clamp.depth(cam.focus + arm * t, up) > 0.0 {
+                    break;
+                }
+                clear = t;
+            }
+
+            // refine between the last clear point and the first blocked one
+            let (mut low, mut high) = (clear, clear + 1.0 / RADIUS_STEPS as f32);
+            for _ in 0..8 {
+                let mid = (low + high) / 2.0;
+                if clamp.depth(cam.focus + arm * mid, up) > 0.0 {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            cam_transform.translation = cam.focus + arm * low;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, CameraUp, FlatGround, GroundClamp, GroundClampMode, ThirdPersonCamera, UpSource,
+    };
+
+    // where the camera ends up with a floor at `height` and a clearance of 0.2
+    fn position_above_ground(height: f32, mode: GroundClampMode, up: UpSource) -> Vec3 {
+        let mut app = test_app(Duration::from_millis(100));
+        // pitched to look up at the focus, which would put the camera 1.27 below it
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    ground_clamp: Some(GroundClamp {
+                        ground: Box::new(FlatGround { height }),
+                        clearance: 0.2,
+                        mode,
+                    }),
+                    up: CameraUp::new(up),
+                    ..default()
+                },
+                Transform::from_rotation(Quat::from_rotation_x(0.6)),
+            ))
+            .id();
+        app.update();
+        app.world.get::<Transform>(cam).unwrap().translation
+    }
+
+    #[test]
+    fn raise_keeps_clearance_above_the_ground() {
+        let raised = position_above_ground(-0.5, GroundClampMode::Raise, UpSource::default());
+        assert!((raised.y - -0.3).abs() < 1e-4);
+        assert!((raised.z - 2.25 * 0.6_f32.cos()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn shorten_radius_pulls_the_camera_in() {
+        let shortened =
+            position_above_ground(-0.5, GroundClampMode::ShortenRadius, UpSource::default());
+        assert!(shortened.y >= -0.3 - 1e-4);
+        assert!(shortened.length() < 2.25);
+    }
+
+    #[test]
+    fn shorten_radius_raises_when_the_focus_is_too_low() {
+        // the focus at the origin is only 0.1 above the ground
+        let position =
+            position_above_ground(-0.1, GroundClampMode::ShortenRadius, UpSource::default());
+        assert!((position.y - 0.1).abs() < 1e-4);
+        assert!((position.z - 2.25 * 0.6_f32.cos()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn measured_along_the_cameras_up() {
+        let up = Quat::from_rotation_z(0.1) * Vec3::Y;
+        let unclamped = position_above_ground(-10.0, GroundClampMode::Raise, UpSource::Fixed(up));
+        let raised = position_above_ground(-0.5, GroundClampMode::Raise, UpSource::Fixed(up));
+        assert!((raised.dot(up) - -0.3).abs() < 1e-4);
+        // raised straight along up
+        let offset = raised - unclamped;
+        assert!(offset.normalize().abs_diff_eq(up, 1e-4));
+    }
+}
@@ -1,14 +1,66 @@
+mod aim;
+mod aim_assist;
+mod chase;
+mod curve;
+mod device;
+mod facing;
+mod fade;
+mod first_person;
+mod framing;
 mod gamepad;
+mod ground;
+mod look_ahead;
+mod look_at;
 mod mouse;
+mod movement;
+mod occluder;
+mod rig;
+mod stick;
+mod up;
+mod window;
 
-use std::f32::consts::PI;
+use std::f32::consts::{FRAC_PI_2, PI};
 
+use aim::AimPlugin;
+pub use aim::{AimMode, AimState};
+pub use aim_assist::{AimAssist, AimAssistTarget};
 use bevy::{
     prelude::*,
-    window::{CursorGrabMode, PrimaryWindow},
+    render::view::VisibilitySystems,
+    transform::TransformSystem,
+    window::{CursorGrabMode, PrimaryWindow, WindowFocused},
 };
-use gamepad::{orbit_gamepad, GamePadPlugin};
-use mouse::{orbit_mouse, MousePlugin};
+pub use chase::ChaseCamera;
+use chase::{chase, chase_condition};
+pub use curve::DisplacementCurve;
+pub use device::LastInputDevice;
+use device::{active_button, DevicePlugin};
+use facing::FacingPlugin;
+pub use facing::{CameraAim, FacingPolicy, TargetFacing};
+use fade::FadePlugin;
+pub use fade::{TargetFade, TargetFadeHidden};
+use first_person::FirstPersonPlugin;
+pub use first_person::{FirstPerson, FirstPersonEye, FirstPersonHidden};
+pub use framing::FramingComposer;
+use framing::{compose, composer_condition};
+use gamepad::GamePadPlugin;
+use ground::{clamp_to_ground, GroundPlugin};
+pub use ground::{FlatGround, GroundClamp, GroundClampMode, GroundHeight};
+use look_ahead::{look_ahead, look_ahead_condition};
+pub use look_ahead::{LookAhead, TargetVelocity};
+pub use look_at::{LookAt, LookAtTarget};
+use mouse::MousePlugin;
+pub use mouse::{MouseAcceleration, MouseSensitivity};
+pub use movement::CameraBasis;
+use movement::MovementPlugin;
+use occluder::OccluderPlugin;
+pub use occluder::{CameraOccluder, OccluderFade};
+use rig::{rig_focus, rigs_condition, RigPlugin};
+pub use rig::{CameraRig, CameraRigs};
+pub use stick::{DeadzoneMode, ResponseCurve, StickSettings};
+use up::align_up;
+pub use up::{CameraUp, GravityField, PointGravity, UpSource};
+use window::camera_window;
 
 /// # Examples
 ///
@@ -23,22 +75,105 @@ pub struct ThirdPersonCameraPlugin;
 
 impl Plugin for ThirdPersonCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((MousePlugin, GamePadPlugin)).add_systems(
+        // registered here too so the plugin works without a `WindowPlugin`, e.g. on a server
+        app.add_event::<WindowFocused>();
+        // so the camera's settings, curves included, can be edited at runtime through reflection
+        app.register_type::<ThirdPersonCamera>()
+            .register_type::<AimMode>()
+            .register_type::<AimState>()
+            .register_type::<AimAssist>()
+            .register_type::<CameraFocusModifier>()
+            .register_type::<CameraRig>()
+            .register_type::<CameraRigs>()
+            .register_type::<CameraUp>()
+            .register_type::<ChaseCamera>()
+            .register_type::<CustomGamepadSettings>()
+            .register_type::<DeadzoneMode>()
+            .register_type::<DisplacementCurve>()
+            .register_type::<FacingPolicy>()
+            .register_type::<FirstPerson>()
+            .register_type::<FramingComposer>()
+            .register_type::<GroundClamp>()
+            .register_type::<GroundClampMode>()
+            .register_type::<InvertAxes>()
+            .register_type::<LookAhead>()
+            .register_type::<LookAt>()
+            .register_type::<LookAtTarget>()
+            .register_type::<MouseAcceleration>()
+            .register_type::<MouseSensitivity>()
+            .register_type::<OccluderFade>()
+            .register_type::<Offset>()
+            .register_type::<ResponseCurve>()
+            .register_type::<StickSettings>()
+            .register_type::<TargetFacing>()
+            .register_type::<TargetFade>()
+            .register_type::<Zoom>();
+        app.add_plugins((
+            AimPlugin,
+            DevicePlugin,
+            MousePlugin,
+            GamePadPlugin,
+            FirstPersonPlugin,
+            FadePlugin,
+            OccluderPlugin,
+            GroundPlugin,
+            MovementPlugin,
+            FacingPlugin,
+            RigPlugin,
+        ))
+        .configure_sets(
+            PostUpdate,
+            ThirdPersonCameraSet
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::VisibilityPropagate)
+                .before(VisibilitySystems::UpdateOrthographicFrusta)
+                .before(VisibilitySystems::UpdatePerspectiveFrusta)
+                .before(VisibilitySystems::UpdateProjectionFrusta),
+        )
+        .add_systems(
             Update,
             (
                 aim.run_if(aim_condition),
+                toggle_x_offset.run_if(toggle_x_offset_condition),
+                toggle_cursor.run_if(toggle_cursor_condition),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            (
                 (
-                    sync_true_focus.after(orbit_mouse).after(orbit_gamepad),
+                    align_up,
+                    sync_true_focus,
+                    chase.run_if(chase_condition),
                     modify_focus.run_if(focus_modifier_condition),
+                    rig_focus.run_if(rigs_condition),
+                    look_ahead.run_if(look_ahead_condition),
+                    compose.run_if(composer_condition),
+                    position_camera,
                 )
                     .chain(),
-                toggle_x_offset.run_if(toggle_x_offset_condition),
-                toggle_cursor.run_if(toggle_cursor_condition),
-            ),
+                (sync_camera_global_transform, propagate_camera_children)
+                    .chain()
+                    .after(clamp_to_ground),
+            )
+                .in_set(ThirdPersonCameraSet),
         );
     }
 }
 
+/// The systems that place the camera around its target. They run in `PostUpdate` after transform
+/// propagation, so the target's [`GlobalTransform`] is up to date, and write the camera's own
+/// [`GlobalTransform`], and those of its descendants, before frusta and visibility are computed.
+/// Systems that need the camera's final position, such as a reticle, can be ordered after this
+/// set. The camera's [`Transform`] never includes the [`LookAt`] or the chase bank, only its
+/// [`GlobalTransform`] does
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ThirdPersonCameraSet;
+
+/// The camera's [`Transform`] is always in world space, so the camera must not have a parent. A
+/// parent's transform is ignored, to follow a ship or platform parent the target to it instead
+/// and see `inherit_frame_rotation`
+///
 /// # Examples
 ///
 /// ```
@@ -51,30 +186,70 @@ impl Plugin for ThirdPersonCameraPlugin {
 ///     ));
 /// }
 /// ```
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct ThirdPersonCamera {
     pub aim_enabled: bool,
     pub aim_button: MouseButton,
+    /// Whether the aim buttons are held, toggled or both to aim
+    pub aim_mode: AimMode,
+    // this should only be edited by the program
+    pub aim_state: AimState,
     pub aim_speed: f32,
     pub aim_zoom: f32,
     pub cursor_lock_toggle_enabled: bool,
     pub cursor_lock_active: bool,
     pub cursor_lock_key: KeyCode,
+    /// How the cursor is held while the cursor lock is active. `Locked` keeps it in place,
+    /// `Confined` keeps it inside the window
+    pub cursor_grab_mode: CursorGrabMode,
+    /// Clicking back into the window grabs the cursor again after it was released by the window
+    /// losing focus or by pressing Escape
+    pub cursor_grab_on_click: bool,
+    /// Pressing Escape releases the cursor
+    pub cursor_release_on_escape: bool,
     pub true_focus: Vec3,
     // this should only be edited by the program
     pub focus: Vec3,
     pub focus_modifier: Option<CameraFocusModifier>,
+    /// Turns the camera along with the parent of the target, such as a rotating platform, ship or
+    /// mount, so the view stays steady relative to it. Only the turn around the up axis is followed
+    pub inherit_frame_rotation: bool,
+    /// Swings the camera behind the target's direction of movement, for vehicles
+    pub chase: Option<ChaseCamera>,
+    /// Lets the focus move within screen space dead & soft zones before the camera follows
+    pub composer: Option<FramingComposer>,
+    /// The up direction that orbiting, the pitch limits and the focus modifier work relative to
+    pub up: CameraUp,
+    /// How the target is turned to face its movement or the camera
+    pub facing: TargetFacing,
+    pub first_person: Option<FirstPerson>,
     pub gamepad_settings: CustomGamepadSettings,
-    pub mouse_sensitivity: f32,
+    pub ground_clamp: Option<GroundClamp>,
+    /// Leads the focus in the direction the target is moving
+    pub look_ahead: Option<LookAhead>,
+    /// Turns the view towards another entity or point, blended by its weight
+    pub look_at: Option<LookAt>,
+    /// Degrees the camera rotates per count of mouse motion, per axis and while aiming
+    pub mouse_sensitivity: MouseSensitivity,
+    /// Inverts mouse orbiting on either axis
+    pub mouse_invert: InvertAxes,
     pub mouse_orbit_button_enabled: bool,
     pub mouse_orbit_button: MouseButton,
+    pub occluder_fade: Option<OccluderFade>,
     pub offset_enabled: bool,
     pub offset: Offset,
     pub offset_toggle_enabled: bool,
     pub offset_toggle_key: KeyCode,
     pub offset_toggle_speed: f32,
+    /// Radius, focus height, shoulder offset and field of view at the top, middle and bottom of
+    /// the orbit, blended by the camera's pitch. While set, `focus_modifier` is ignored
+    pub rigs: Option<CameraRigs>,
+    pub target_fade: Option<TargetFade>,
     pub zoom_enabled: bool,
     pub zoom: Zoom,
+    /// Scales how far each line of mouse scrolling, or each second of holding a gamepad zoom
+    /// button, moves the camera
     pub zoom_sensitivity: f32,
 }
 
@@ -83,23 +258,41 @@ impl Default for ThirdPersonCamera {
         ThirdPersonCamera {
             aim_enabled: false,
             aim_button: MouseButton::Right,
+            aim_mode: AimMode::default(),
+            aim_state: AimState::default(),
             aim_speed: 3.0,
             aim_zoom: 0.7,
             cursor_lock_key: KeyCode::Space,
             cursor_lock_toggle_enabled: true,
+            cursor_grab_mode: CursorGrabMode::Locked,
+            cursor_grab_on_click: true,
+            cursor_release_on_escape: false,
             focus: Vec3::ZERO,
             true_focus: Vec3::ZERO,
             focus_modifier: None,
+            inherit_frame_rotation: false,
+            chase: None,
+            composer: None,
+            up: CameraUp::default(),
+            facing: TargetFacing::default(),
+            first_person: None,
             gamepad_settings: CustomGamepadSettings::default(),
+            ground_clamp: None,
+            look_ahead: None,
+            look_at: None,
             cursor_lock_active: true,
-            mouse_sensitivity: 1.0,
+            mouse_sensitivity: MouseSensitivity::default(),
+            mouse_invert: InvertAxes::default(),
             mouse_orbit_button_enabled: false,
             mouse_orbit_button: MouseButton::Middle,
+            occluder_fade: None,
             offset_enabled: false,
             offset: Offset::new(0.5, 0.4),
             offset_toggle_enabled: false,
             offset_toggle_speed: 5.0,
+            rigs: None,
             offset_toggle_key: KeyCode::E,
+            target_fade: None,
             zoom_enabled: true,
             zoom: Zoom::new(1.5, 3.0),
             zoom_sensitivity: 1.0,
@@ -107,7 +300,38 @@ impl Default for ThirdPersonCamera {
     }
 }
 
-#[derive(Clone, Copy)]
+/// Moves the focus and the camera as it goes high or low, so the target stays in view. Above
+/// `upper_threshold` the focus moves forwards, below `lower_threshold` it moves backwards and the
+/// camera moves towards it. Each movement is shaped by a [`DisplacementCurve`] mapping how far past
+/// the threshold the camera is, from 0 to 1, to the fraction of the maximum displacement
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{CameraFocusModifier, DisplacementCurve, ThirdPersonCamera};
+/// use std::f32::consts::PI;
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             focus_modifier: Some(CameraFocusModifier {
+///                 lower_threshold: PI / 2.,
+///                 upper_threshold: 2. * PI / 3.,
+///                 max_forward_displacement: 0.5,
+///                 max_backward_displacement: 0.75,
+///                 lower_displacement_curve: DisplacementCurve::Smoothstep,
+///                 upper_displacement_curve: DisplacementCurve::Smoothstep,
+///                 behind_radius_displacement: 2.0,
+///                 lower_radius_curve: DisplacementCurve::Exponential { rate: 4. },
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CameraFocusModifier {
     /// Maximum distance that the focus can move forwards when camera goes high
     pub max_forward_displacement: f32,
@@ -117,16 +341,16 @@ pub struct CameraFocusModifier {
     pub upper_threshold: f32,
     /// Must be less than Pi / 2
     pub lower_threshold: f32,
-    /// Function mapping an input angle to [0,1] that shows the forward displacement
-    pub upper_displacement_function: fn(f32) -> f32,
-    /// Function mapping an input angle to [0,1] that shows the backward displacement
-    pub lower_displacement_function: fn(f32) -> f32,
+    /// Curve mapping an input angle to [0,1] that shows the forward displacement
+    pub upper_displacement_curve: DisplacementCurve,
+    /// Curve mapping an input angle to [0,1] that shows the backward displacement
+    pub lower_displacement_curve: DisplacementCurve,
     /// Determines the maximum displacement of the camera towards the focus when the camera goes
     /// low
     pub behind_radius_displacement: f32,
-    /// Function mapping an input angle to [0,1] that shows camera displacement towards focus when
+    /// Curve mapping an input angle to [0,1] that shows camera displacement towards focus when
     /// camera goes low
-    pub lower_radius_function: fn(f32) -> f32,
+    pub lower_radius_curve: DisplacementCurve,
 }
 
 impl Default for CameraFocusModifier {
@@ -136,10 +360,10 @@ impl Default for CameraFocusModifier {
             max_backward_displacement: 0.,
             upper_threshold: PI,
             lower_threshold: 0.,
-            upper_displacement_function: |_a| 0.,
-            lower_displacement_function: |_a| 0.,
+            upper_displacement_curve: DisplacementCurve::default(),
+            lower_displacement_curve: DisplacementCurve::default(),
             behind_radius_displacement: 0.,
-            lower_radius_function: |_a| 0.,
+            lower_radius_curve: DisplacementCurve::default(),
         }
     }
 }
@@ -151,47 +375,45 @@ pub fn modify_focus(mut cam_q: Query<(&mut ThirdPersonCamera, &Transform)>) {
         return;
     };
 
-    let modifier = cam.focus_modifier.unwrap();
+    let cam = &mut *cam;
+    let modifier = cam.focus_modifier.as_ref().unwrap();
+    let up = cam.up.current();
 
-    // angle is 0 - Pi, with Pi / 2 as directly behind and parallel to the xz plane
-    let vec = cam.true_focus - transform.translation;
-    let angle = vec.normalize().dot(Vec3::Y.normalize()).acos();
+    // angle is 0 - Pi, with Pi / 2 as directly behind and level with the focus relative to up. It
+    // is taken from the rotation as the camera is only placed around the new focus afterwards
+    let angle = transform.forward().dot(up).clamp(-1.0, 1.0).acos();
     if angle > modifier.upper_threshold {
         // theta is bound between 0 - 1 (close enough, must be rounded here most likely)
         let theta =
             ((angle - modifier.upper_threshold) / (PI - modifier.upper_threshold)).clamp(0.0, 1.0);
         // focus_disp is bound between 0 - 1 (close enough again)
-        let focus_disp = (modifier.upper_displacement_function)(theta).clamp(0.0, 1.0);
-        // actual change in the xz direction, ranges from 0 - max_forward_displacement
+        let focus_disp = modifier
+            .upper_displacement_curve
+            .sample(theta)
+            .clamp(0.0, 1.0);
+        // actual change along the ground, ranges from 0 - max_forward_displacement
         let displacement = focus_disp * modifier.max_forward_displacement;
         // move the focus "forward" by focus_disp
-        let xz = transform.forward().xz().normalize() * displacement;
+        let along = transform.forward().reject_from(up).normalize_or_zero() * displacement;
         // updates the focus to be the true focus plus the displacement found before
-        cam.focus = (
-            cam.true_focus.x + xz.x,
-            cam.true_focus.y,
-            cam.true_focus.z + xz.y,
-        )
-            .into();
+        cam.focus = cam.true_focus + along;
     } else if angle < modifier.lower_threshold {
         // theta is bound between 0 - 1 (close enough, must be rounded here most likely)
         let theta =
             ((angle - modifier.lower_threshold) / -modifier.lower_threshold).clamp(0.0, 1.0);
         // focus_disp is bound between 0 - 1 (close enough again)
-        let focus_disp = (modifier.lower_displacement_function)(theta).clamp(0.0, 1.0);
-        // actual change in the xz direction, ranges from 0 - max_forward_displacement
+        let focus_disp = modifier
+            .lower_displacement_curve
+            .sample(theta)
+            .clamp(0.0, 1.0);
+        // actual change along the ground, ranges from 0 - max_backward_displacement
         let displacement = focus_disp * modifier.max_backward_displacement;
-        // move the focus "forward" by focus_disp
-        let xz = transform.back().xz().normalize() * displacement;
+        // move the focus "backward" by focus_disp
+        let along = transform.back().reject_from(up).normalize_or_zero() * displacement;
         // updates the focus to be the true focus plus the displacement found before
-        cam.focus = (
-            cam.true_focus.x + xz.x,
-            cam.true_focus.y,
-            cam.true_focus.z + xz.y,
-        )
-            .into();
+        cam.focus = cam.true_focus + along;
         // move the camera closer to the focus when looking upwards
-        let radius_disp = (modifier.lower_radius_function)(theta);
+        let radius_disp = modifier.lower_radius_curve.sample(theta);
         let radius_change = radius_disp * -modifier.behind_radius_displacement;
         cam.zoom.radius = cam.zoom.true_radius + radius_change;
     } else {
@@ -203,10 +425,41 @@ fn focus_modifier_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
     let Ok(cam) = cam_q.get_single() else {
         return false;
     };
-    cam.focus_modifier.is_some()
+    // rigs replace the focus modifier, the two would both move the focus and the radius
+    cam.focus_modifier.is_some() && cam.rigs.is_none()
+}
+
+/// Flips orbit input on either axis. Every orbit input path, mouse and gamepad alike, passes its
+/// deltas through this before turning the camera
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::InvertAxes;
+/// let inverted_y = InvertAxes { x: false, y: true };
+/// assert_eq!(inverted_y.apply(Vec2::new(1.0, 2.0)), Vec2::new(1.0, -2.0));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+pub struct InvertAxes {
+    /// Invert horizontal orbiting
+    pub x: bool,
+    /// Invert vertical orbiting
+    pub y: bool,
+}
+
+impl InvertAxes {
+    /// Negates the axes of `delta` that are inverted
+    pub fn apply(&self, delta: Vec2) -> Vec2 {
+        Vec2::new(
+            if self.x { -delta.x } else { delta.x },
+            if self.y { -delta.y } else { delta.y },
+        )
+    }
 }
 
 /// Sets the zoom bounds (min & max)
+#[derive(Reflect)]
 pub struct Zoom {
     pub min: f32,
     pub max: f32,
@@ -229,6 +482,7 @@ impl Zoom {
 
 /// Offset the camera behind the player. For example, an offset value of (0.5, 0.25) will
 /// place the camera closer the player's right shoulder
+#[derive(Reflect)]
 pub struct Offset {
     pub offset: (f32, f32),
     offset_copy: (f32, f32),
@@ -245,6 +499,7 @@ impl Offset {
     }
 }
 
+/// The gamepad whose input the camera uses, the one that last pressed a button
 #[derive(Resource)]
 pub struct GamepadResource(pub Gamepad);
 
@@ -263,10 +518,11 @@ pub struct GamepadResource(pub Gamepad);
 ///                aim_button: GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger2),
 ///                mouse_orbit_button: GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger),
 ///                offset_toggle_button: GamepadButton::new(gamepad, GamepadButtonType::DPadRight),
-///                x_sensitivity: 7.0,
-///                y_sensitivity: 4.0,
+///                x_sensitivity: 120.0,
+///                y_sensitivity: 60.0,
 ///                zoom_in_button: GamepadButton::new(gamepad, GamepadButtonType::DPadUp),
 ///                zoom_out_button: GamepadButton::new(gamepad, GamepadButtonType::DPadDown),
+///                ..default()
 ///            },
 ///            ..default()
 ///        },
@@ -274,12 +530,20 @@ pub struct GamepadResource(pub Gamepad);
 ///    ));
 /// }
 /// ```
-#[derive(Component)]
+#[derive(Component, Reflect)]
 pub struct CustomGamepadSettings {
     pub aim_button: GamepadButton,
+    /// Slows the camera down over targets and pulls it towards them while aiming
+    pub aim_assist: Option<AimAssist>,
+    /// Inverts gamepad orbiting on either axis
+    pub invert: InvertAxes,
     pub mouse_orbit_button: GamepadButton,
     pub offset_toggle_button: GamepadButton,
+    /// Deadzones and response curve of the right stick used to orbit
+    pub orbit_stick: StickSettings,
+    /// Degrees per second the camera yaws with the stick fully deflected
     pub x_sensitivity: f32,
+    /// Degrees per second the camera pitches with the stick fully deflected
     pub y_sensitivity: f32,
     pub zoom_in_button: GamepadButton,
     pub zoom_out_button: GamepadButton,
@@ -290,10 +554,13 @@ impl Default for CustomGamepadSettings {
         let gamepad = Gamepad::new(0);
         Self {
             aim_button: GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger2),
+            aim_assist: None,
+            invert: InvertAxes::default(),
             mouse_orbit_button: GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger),
             offset_toggle_button: GamepadButton::new(gamepad, GamepadButtonType::DPadRight),
-            x_sensitivity: 7.0,
-            y_sensitivity: 4.0,
+            orbit_stick: StickSettings::default(),
+            x_sensitivity: 120.0,
+            y_sensitivity: 60.0,
             zoom_in_button: GamepadButton::new(gamepad, GamepadButtonType::DPadUp),
             zoom_out_button: GamepadButton::new(gamepad, GamepadButtonType::DPadDown),
         }
@@ -317,19 +584,159 @@ impl Default for CustomGamepadSettings {
 #[derive(Component)]
 pub struct ThirdPersonCameraTarget;
 
-// Updates the location of the camera's true focus, or the location of the typical camera focus
+// updates the location of the camera's true focus from the target's `GlobalTransform`, so targets
+// parented to a ship, elevator or mount are followed too. The focus starts out at the true focus
+// and is moved from there by the focus modifier
 fn sync_true_focus(
-    player_q: Query<&Transform, With<ThirdPersonCameraTarget>>,
-    mut cam_q: Query<&mut ThirdPersonCamera, Without<ThirdPersonCameraTarget>>,
+    player_q: Query<(&GlobalTransform, Option<&Parent>), With<ThirdPersonCameraTarget>>,
+    frame_q: Query<&GlobalTransform>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, &mut Transform), Without<ThirdPersonCameraTarget>>,
+    mut last_frame: Local<Option<(Entity, Quat)>>,
 ) {
-    let Ok(player) = player_q.get_single() else {
+    let Ok((player, parent)) = player_q.get_single() else {
         return;
     };
-    let Ok(mut cam) = cam_q.get_single_mut() else {
+    let Ok((mut cam, mut cam_transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    let up = cam.up.current();
+    cam.true_focus = player.translation() + up * 0.81;
+    cam.focus = cam.true_focus;
+
+    // the rotation of the frame the target moves in, such as a rotating platform
+    let frame = parent.and_then(|parent| {
+        let rotation = frame_q.get(parent.get()).ok()?.compute_transform().rotation;
+        Some((parent.get(), rotation))
+    });
+    if let (Some((entity, rotation)), Some((last_entity, last_rotation))) = (frame, *last_frame) {
+        if cam.inherit_frame_rotation && entity == last_entity {
+            let turn = twist(rotation * last_rotation.inverse(), up);
+            cam_transform.rotation = turn * cam_transform.rotation;
+        }
+    }
+    *last_frame = frame;
+}
+
+// the part of `rotation` that turns around `axis`, so following it keeps the horizon level
+fn twist(rotation: Quat, axis: Vec3) -> Quat {
+    let projected = axis * Vec3::new(rotation.x, rotation.y, rotation.z).dot(axis);
+    let twist = Quat::from_xyzw(projected.x, projected.y, projected.z, rotation.w);
+    if twist.length_squared() > f32::EPSILON {
+        twist.normalize()
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+// the distance from the focus to a camera at `rotation`, the zoom radius scaled by the rig
+pub(crate) fn effective_radius(cam: &ThirdPersonCamera, rotation: Quat) -> f32 {
+    match cam.rigs.as_ref() {
+        Some(rigs) => cam.zoom.radius * rigs.sample_rotation(rotation, cam.up.current()).radius,
+        None => cam.zoom.radius,
+    }
+}
+
+// places the camera around the focus at its orbit rotation, zoom radius and shoulder offset
+pub(crate) fn position_camera(
+    mut cam_q: Query<(&ThirdPersonCamera, &mut Transform)>,
+    last_device: Res<LastInputDevice>,
+) {
+    let Ok((cam, mut cam_transform)) = cam_q.get_single_mut() else {
         return;
     };
 
-    cam.true_focus = player.translation + Vec3::new(0., 0.81, 0.);
+    let rot_matrix = Mat3::from_quat(cam_transform.rotation);
+    let radius = effective_radius(cam, cam_transform.rotation);
+    let mut shoulder = cam.offset.offset;
+    if let Some(rigs) = cam.rigs.as_ref() {
+        let rig = rigs.sample_rotation(cam_transform.rotation, cam.up.current());
+        // follow the shoulder toggle, from 1 on the starting shoulder to -1 on the other
+        let side = if cam.offset.offset_copy.0 != 0.0 {
+            cam.offset.offset.0 / cam.offset.offset_copy.0
+        } else {
+            1.0
+        };
+        shoulder = (rig.offset.0 * side, rig.offset.1);
+    }
+
+    // apply the offset if offset_enabled is true. With the mouse it only applies while aiming
+    let aiming_or_gamepad =
+        cam.aim_state.is_active() || matches!(*last_device, LastInputDevice::Gamepad(_));
+    let mut offset = Vec3::ZERO;
+    if cam.offset_enabled && aiming_or_gamepad {
+        offset = rot_matrix.mul_vec3(Vec3::new(shoulder.0, shoulder.1, 0.0));
+    }
+    cam_transform.translation =
+        cam.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, radius)) + offset;
+}
+
+// transform propagation has already run this frame, so the camera's final pose, including the
+// `LookAt` and the chase bank, is written to its `GlobalTransform` directly. The pose is in world
+// space, so any parent the camera has is ignored
+fn sync_camera_global_transform(
+    mut cam_q: Query<(&ThirdPersonCamera, &Transform, &mut GlobalTransform)>,
+    global_q: Query<&GlobalTransform, Without<ThirdPersonCamera>>,
+) {
+    let Ok((cam, cam_transform, mut global)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    // the look at and the chase bank only turn the view, the transform keeps the orbit rotation.
+    // The look at is skipped while aiming, so the view matches the aim ray
+    let mut local = *cam_transform;
+    let look_at = cam.look_at.as_ref().filter(|_| !cam.aim_state.is_active());
+    if let Some(look_at) = look_at {
+        let point = match look_at.target {
+            LookAtTarget::Entity(entity) => global_q.get(entity).ok().map(|t| t.translation()),
+            LookAtTarget::Point(point) => Some(point),
+        };
+        if let Some(point) = point {
+            local.rotation = look_at.rotation(
+                local.translation,
+                local.rotation,
+                cam.focus,
+                point,
+                cam.up.current(),
+            );
+        }
+    }
+    if let Some(chase) = cam.chase.as_ref() {
+        local.rotation *= chase.roll();
+    }
+
+    let new_global = GlobalTransform::from(local);
+    if *global != new_global {
+        *global = new_global;
+    }
+}
+
+// the camera is placed after transform propagation, so its descendants, such as a weapon or a
+// light, are moved along with its final pose here instead of a frame late
+fn propagate_camera_children(
+    cam_q: Query<(&GlobalTransform, &Children), With<ThirdPersonCamera>>,
+    mut child_q: Query<
+        (&Transform, &mut GlobalTransform, Option<&Children>),
+        Without<ThirdPersonCamera>,
+    >,
+) {
+    let Ok((global, children)) = cam_q.get_single() else {
+        return;
+    };
+
+    let mut stack: Vec<_> = children.iter().map(|&child| (*global, child)).collect();
+    while let Some((parent, entity)) = stack.pop() {
+        let Ok((transform, mut child_global, grandchildren)) = child_q.get_mut(entity) else {
+            continue;
+        };
+        let new_global = parent.mul_transform(*transform);
+        if *child_global != new_global {
+            *child_global = new_global;
+        }
+        if let Some(grandchildren) = grandchildren {
+            stack.extend(grandchildren.iter().map(|&child| (new_global, child)));
+        }
+    }
 }
 
 // only run aiming logic if `aim_enabled` is true
@@ -340,30 +747,13 @@ fn aim_condition(cam_q: Query<&ThirdPersonCamera, With<ThirdPersonCamera>>) -> b
     cam.aim_enabled
 }
 
-fn aim(
-    mut cam_q: Query<
-        (&mut ThirdPersonCamera, &Transform),
-        (With<ThirdPersonCamera>, Without<ThirdPersonCameraTarget>),
-    >,
-    mouse: Res<Input<MouseButton>>,
-    mut player_q: Query<&mut Transform, With<ThirdPersonCameraTarget>>,
-    btns: Res<Input<GamepadButton>>,
-    time: Res<Time>,
-) {
-    let Ok((mut cam, cam_transform)) = cam_q.get_single_mut() else {
+// zooms in while aiming, turning the target to face the aim is handled by `TargetFacing`
+fn aim(mut cam_q: Query<&mut ThirdPersonCamera>, time: Res<Time>) {
+    let Ok(mut cam) = cam_q.get_single_mut() else {
         return;
     };
 
-    // check if aim button was pressed
-    let aim_btn = mouse.pressed(cam.aim_button) || btns.pressed(cam.gamepad_settings.aim_button);
-
-    if aim_btn {
-        // rotate player or target to face direction he is aiming
-        let Ok(mut player_transform) = player_q.get_single_mut() else {
-            return;
-        };
-        player_transform.look_to(cam_transform.forward(), Vec3::Y);
-
+    if cam.aim_state.is_active() {
         let desired_zoom = cam.zoom.min * cam.aim_zoom;
 
         // radius_copy is used for restoring the radius (zoom) to it's
@@ -401,7 +791,50 @@ pub fn zoom_condition(cam_q: Query<&ThirdPersonCamera, With<ThirdPersonCamera>>)
     let Ok(cam) = cam_q.get_single() else {
         return false;
     };
-    return cam.zoom_enabled && cam.cursor_lock_active;
+    cam.zoom_enabled && cam.cursor_lock_active
+}
+
+// how close, in radians, the camera may pitch to looking straight up or down
+pub(crate) const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+
+// rotates the camera by `yaw` around `up` and by `pitch` around its own x axis. pitch is clamped
+// rather than rejected at the vertical bounds so that the same input lands on the same pose no
+// matter how many frames it is spread across
+pub(crate) fn orbit(rotation: &mut Quat, up: Vec3, yaw: f32, pitch: f32) {
+    *rotation = Quat::from_axis_angle(up, yaw) * *rotation; // rotate around the up axis
+
+    let current_pitch = (*rotation * Vec3::NEG_Z).dot(up).clamp(-1.0, 1.0).asin();
+    let new_pitch = (current_pitch + pitch).clamp(-MAX_PITCH, MAX_PITCH);
+    *rotation *= Quat::from_rotation_x(new_pitch - current_pitch);
+}
+
+// moves `current` towards `target` with exponential smoothing. `damping` is how quickly the gap
+// closes, per second. A frame's share of the gap depends on the frame's length, so for a fixed
+// target several short frames end up where one long frame of the same total length would
+pub(crate) fn smooth<T>(current: T, target: T, damping: f32, delta_seconds: f32) -> T
+where
+    T: Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<f32, Output = T>,
+{
+    let t = 1.0 - (-damping.max(0.0) * delta_seconds).exp();
+    current + (target - current) * t
+}
+
+// scales the radius by `lines` of scrolling, positive values zoom in. Zooming is exponential so
+// that one large step and many small ones that add up to it give the same radius
+pub(crate) fn zoom(cam: &mut ThirdPersonCamera, lines: f32) {
+    // aiming zooms in past the minimum, so check the radius the camera returns to afterwards
+    let at_min = cam.zoom.radius_copy.unwrap_or(cam.zoom.radius) <= cam.zoom.min;
+    if let Some(first_person) = cam.first_person.as_mut() {
+        if first_person.zoom(lines, at_min) {
+            return;
+        }
+    }
+
+    let new_radius = cam.zoom.radius * (-lines * 0.1 * cam.zoom_sensitivity).exp();
+    cam.zoom.radius = new_radius.clamp(cam.zoom.min, cam.zoom.max);
 }
 
 // only run toggle_x_offset if `offset_toggle_enabled` is true
@@ -418,6 +851,7 @@ fn toggle_x_offset(
     keys: Res<Input<KeyCode>>,
     time: Res<Time>,
     btns: Res<Input<GamepadButton>>,
+    gamepad_res: Option<Res<GamepadResource>>,
 ) {
     let Ok(mut cam) = cam_q.get_single_mut() else {
         return;
@@ -425,7 +859,10 @@ fn toggle_x_offset(
 
     // check if toggle btn was pressed
     let toggle_btn = keys.just_pressed(cam.offset_toggle_key)
-        || btns.just_pressed(cam.gamepad_settings.offset_toggle_button);
+        || btns.just_pressed(active_button(
+            gamepad_res.as_deref(),
+            cam.gamepad_settings.offset_toggle_button,
+        ));
 
     if toggle_btn {
         // Switch direction by inverting the offset_flag
@@ -444,26 +881,67 @@ fn toggle_x_offset(
         .clamp(-cam.offset.offset_copy.0, cam.offset.offset_copy.0);
 }
 
+// the cursor is grabbed in the window the camera renders to. Cursor management is skipped when
+// the camera has no window, such as on a dedicated server, in a headless simulation or when
+// rendering to an image, while orbiting and zooming keep working from the input resources.
+// The cursor is released when the window loses focus and grabbed again by clicking into it. The
+// window is only written to when the cursor state actually changes
 fn toggle_cursor(
-    mut cam_q: Query<&mut ThirdPersonCamera>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, Option<&Camera>)>,
     keys: Res<Input<KeyCode>>,
-    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+    mouse: Res<Input<MouseButton>>,
+    mut focus_evr: EventReader<WindowFocused>,
+    primary_window_q: Query<Entity, With<PrimaryWindow>>,
+    mut window_q: Query<&mut Window>,
+    // set when the cursor was released by losing focus or pressing escape rather than by the lock
+    // key, so that clicking back into the window grabs it again
+    mut released: Local<bool>,
 ) {
-    let Ok(mut cam) = cam_q.get_single_mut() else {
+    let Ok((mut cam, camera)) = cam_q.get_single_mut() else {
         return;
     };
+    let window_entity = camera_window(camera, primary_window_q.get_single().ok());
 
     if keys.just_pressed(cam.cursor_lock_key) {
         cam.cursor_lock_active = !cam.cursor_lock_active;
+        *released = false;
     }
 
-    let mut window = window_q.get_single_mut().unwrap();
-    if cam.cursor_lock_active {
-        window.cursor.grab_mode = CursorGrabMode::Locked;
-        window.cursor.visible = false;
+    // release the cursor when alt-tabbing away instead of leaving it grabbed in the background
+    let focus_lost = focus_evr
+        .read()
+        .any(|ev| Some(ev.window) == window_entity && !ev.focused);
+    let escape = cam.cursor_release_on_escape && keys.just_pressed(KeyCode::Escape);
+    if cam.cursor_lock_active && (focus_lost || escape) {
+        cam.cursor_lock_active = false;
+        *released = true;
+    }
+
+    let Some(mut window) = window_entity.and_then(|window| window_q.get_mut(window).ok()) else {
+        return;
+    };
+
+    if *released
+        && cam.cursor_grab_on_click
+        && window.focused
+        && mouse.just_pressed(MouseButton::Left)
+    {
+        cam.cursor_lock_active = true;
+        *released = false;
+    }
+
+    let (grab_mode, visible) = if cam.cursor_lock_active {
+        (cam.cursor_grab_mode, false)
     } else {
-        window.cursor.grab_mode = CursorGrabMode::None;
-        window.cursor.visible = true;
+        (CursorGrabMode::None, true)
+    };
+
+    // only write to the window when the state changes, so it isn't marked as changed every frame
+    if window.cursor.grab_mode != grab_mode {
+        window.cursor.grab_mode = grab_mode;
+    }
+    if window.cursor.visible != visible {
+        window.cursor.visible = visible;
     }
 }
 
@@ -474,3 +952,389 @@ fn toggle_cursor_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
     };
     cam.cursor_lock_toggle_enabled
 }
+
+// closures and trait objects can't be reflected, so these stand in for them when a
+// `DisplacementCurve::Custom`, a `ResponseCurve::Custom` or a `GroundClamp` is built through
+// reflection. The public docs of each field say what the stand-in does
+pub(crate) fn unreflected_curve() -> std::sync::Arc<dyn Fn(f32) -> f32 + Send + Sync> {
+    std::sync::Arc::new(|_| 0.0)
+}
+
+pub(crate) fn unreflected_response() -> fn(f32) -> f32 {
+    |value| value
+}
+
+pub(crate) fn unreflected_ground() -> Box<dyn GroundHeight> {
+    Box::new(FlatGround::default())
+}
+
+// an app running the camera plugin, where every update advances time by `step`
+#[cfg(test)]
+pub(crate) fn test_app(step: std::time::Duration) -> App {
+    use bevy::{input::InputPlugin, time::TimeUpdateStrategy};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, ThirdPersonCameraPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(step));
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::TypeId, f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::{
+        input::{
+            keyboard::KeyboardInput,
+            mouse::{MouseButtonInput, MouseMotion},
+            ButtonState,
+        },
+        prelude::*,
+        reflect::GetPath,
+        transform::TransformPlugin,
+        window::{CursorGrabMode, PrimaryWindow, WindowFocused},
+    };
+
+    use crate::{
+        test_app, CameraAim, CameraRigs, DisplacementCurve, LastInputDevice, LookAt, LookAtTarget,
+        ThirdPersonCamera, ThirdPersonCameraTarget,
+    };
+
+    #[test]
+    fn focus_follows_a_parented_target() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins((TransformPlugin, HierarchyPlugin));
+        // the target stands 2 units along the local z axis of a platform at x = 10
+        let platform = app
+            .world
+            .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+                10.0, 0.0, 0.0,
+            )))
+            .id();
+        app.world
+            .spawn((
+                ThirdPersonCameraTarget,
+                SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, 2.0)),
+            ))
+            .set_parent(platform);
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    inherit_frame_rotation: true,
+                    ..default()
+                },
+                SpatialBundle::default(),
+            ))
+            .id();
+        app.update();
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(10.0, 0.81, 2.0), 1e-4));
+
+        // a quarter turn of the platform carries the target and turns the camera with it
+        app.world
+            .get_mut::<Transform>(platform)
+            .unwrap()
+            .rotate_y(FRAC_PI_2);
+        app.update();
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(12.0, 0.81, 0.0), 1e-4));
+        let transform = *app.world.get::<Transform>(cam).unwrap();
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(FRAC_PI_2), 1e-4));
+
+        // the camera's `GlobalTransform` already has this frame's position
+        let global = app.world.get::<GlobalTransform>(cam).unwrap();
+        assert!(global
+            .translation()
+            .abs_diff_eq(transform.translation, 1e-4));
+    }
+
+    #[test]
+    fn parented_camera_is_placed_in_world_space() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins((TransformPlugin, HierarchyPlugin));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            SpatialBundle::from_transform(Transform::from_xyz(10.0, 0.0, 0.0)),
+        ));
+        // a moved and turned parent, which the camera ignores
+        let parent = app
+            .world
+            .spawn(SpatialBundle::from_transform(
+                Transform::from_xyz(0.0, 5.0, -3.0).with_rotation(Quat::from_rotation_y(1.0)),
+            ))
+            .id();
+        let cam = app
+            .world
+            .spawn((ThirdPersonCamera::default(), SpatialBundle::default()))
+            .set_parent(parent)
+            .id();
+        for _ in 0..2 {
+            app.update();
+        }
+
+        let transform = *app.world.get::<Transform>(cam).unwrap();
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(10.0, 0.81, 0.0), 1e-4));
+        assert!(transform.translation.distance(focus) > 1.0);
+        let global = app.world.get::<GlobalTransform>(cam).unwrap();
+        assert!(global
+            .compute_transform()
+            .translation
+            .abs_diff_eq(transform.translation, 1e-4));
+        assert!(global
+            .compute_transform()
+            .rotation
+            .abs_diff_eq(transform.rotation, 1e-4));
+    }
+
+    #[test]
+    fn look_at_turns_the_rendered_view_only() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            GlobalTransform::from_translation(Vec3::ZERO),
+        ));
+        let boss_position = Vec3::new(10.0, 0.81, -10.0);
+        let boss = app
+            .world
+            .spawn(GlobalTransform::from_translation(boss_position))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    look_at: Some(LookAt {
+                        target: LookAtTarget::Entity(boss),
+                        weight: 1.0,
+                    }),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world.entity_mut(cam).insert(GlobalTransform::default());
+        app.update();
+
+        // the view turns to the boss while the camera keeps orbiting behind the target
+        let transform = *app.world.get::<Transform>(cam).unwrap();
+        let global = *app.world.get::<GlobalTransform>(cam).unwrap();
+        assert_eq!(transform.forward(), Vec3::NEG_Z);
+        let to_boss = (boss_position - global.translation()).normalize();
+        assert!(global.forward().abs_diff_eq(to_boss, 1e-4));
+
+        // with the shoulder offset the boss is kept where the focus was on screen
+        *app.world.resource_mut::<LastInputDevice>() = LastInputDevice::Gamepad(Gamepad::new(0));
+        app.world
+            .get_mut::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .offset_enabled = true;
+        app.update();
+        let transform = *app.world.get::<Transform>(cam).unwrap();
+        let global = *app.world.get::<GlobalTransform>(cam).unwrap();
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        let on_screen = |rotation: Quat, point: Vec3| {
+            (rotation.inverse() * (point - transform.translation)).normalize()
+        };
+        let (_, rotation, _) = global.to_scale_rotation_translation();
+        assert!(on_screen(rotation, boss_position)
+            .abs_diff_eq(on_screen(transform.rotation, focus), 1e-4));
+    }
+
+    #[test]
+    fn camera_children_follow_the_rendered_view() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            GlobalTransform::from_translation(Vec3::ZERO),
+        ));
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    look_at: Some(LookAt {
+                        target: LookAtTarget::Point(Vec3::new(10.0, 0.81, -10.0)),
+                        weight: 1.0,
+                    }),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world.entity_mut(cam).insert(GlobalTransform::default());
+        let held = Transform::from_xyz(0.3, -0.2, -0.5);
+        let weapon = app
+            .world
+            .spawn((held, GlobalTransform::default()))
+            .set_parent(cam)
+            .id();
+        let sight = app
+            .world
+            .spawn((
+                Transform::from_xyz(0.0, 0.1, 0.0),
+                GlobalTransform::default(),
+            ))
+            .set_parent(weapon)
+            .id();
+        app.update();
+
+        let global = *app.world.get::<GlobalTransform>(cam).unwrap();
+        let weapon_global = *app.world.get::<GlobalTransform>(weapon).unwrap();
+        assert_eq!(weapon_global, global.mul_transform(held));
+        let sight_global = app.world.get::<GlobalTransform>(sight).unwrap();
+        assert_eq!(
+            *sight_global,
+            weapon_global.mul_transform(Transform::from_xyz(0.0, 0.1, 0.0))
+        );
+    }
+
+    #[test]
+    fn aim_point_stays_under_the_reticle_with_look_at() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        app.world.entity_mut(target).insert(Transform::default());
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    aim_enabled: true,
+                    look_at: Some(LookAt {
+                        target: LookAtTarget::Point(Vec3::new(10.0, 0.81, -10.0)),
+                        weight: 1.0,
+                    }),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world.entity_mut(cam).insert(GlobalTransform::default());
+        let button = app.world.get::<ThirdPersonCamera>(cam).unwrap().aim_button;
+        app.world.resource_mut::<Input<MouseButton>>().press(button);
+        for _ in 0..20 {
+            app.update();
+        }
+
+        // the rendered view looks along the aim ray, through the aim point
+        let global = *app.world.get::<GlobalTransform>(cam).unwrap();
+        let aim = app.world.get::<CameraAim>(target).unwrap();
+        assert!(aim.active);
+        let to_aim = (aim.point - global.translation()).normalize();
+        assert!(global.forward().abs_diff_eq(to_aim, 1e-4));
+    }
+
+    #[test]
+    fn orbits_without_a_window_and_toggles_the_lock() {
+        let mut app = test_app(Duration::from_millis(100));
+        let cam = app
+            .world
+            .spawn((ThirdPersonCamera::default(), Transform::default()))
+            .id();
+
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(50.0, 0.0),
+        });
+        app.update();
+        assert_ne!(
+            app.world.get::<Transform>(cam).unwrap().rotation,
+            Quat::IDENTITY
+        );
+
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Space),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        assert!(
+            !app.world
+                .get::<ThirdPersonCamera>(cam)
+                .unwrap()
+                .cursor_lock_active
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct WindowChanges(usize);
+
+    #[test]
+    fn cursor_is_released_on_focus_loss_and_grabbed_on_click() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.init_resource::<WindowChanges>().add_systems(
+            Last,
+            |window_q: Query<(), Changed<Window>>, mut changes: ResMut<WindowChanges>| {
+                changes.0 += window_q.iter().count();
+            },
+        );
+        let window = app.world.spawn((Window::default(), PrimaryWindow)).id();
+        app.world
+            .spawn((ThirdPersonCamera::default(), Camera::default()));
+        let grab_mode = |app: &App| app.world.get::<Window>(window).unwrap().cursor.grab_mode;
+
+        // the window is only written to when the cursor state changes
+        app.update();
+        assert_eq!(grab_mode(&app), CursorGrabMode::Locked);
+        let changes = app.world.resource::<WindowChanges>().0;
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<WindowChanges>().0, changes);
+
+        app.world.get_mut::<Window>(window).unwrap().focused = false;
+        app.world.send_event(WindowFocused {
+            window,
+            focused: false,
+        });
+        app.update();
+        assert_eq!(grab_mode(&app), CursorGrabMode::None);
+
+        app.world.get_mut::<Window>(window).unwrap().focused = true;
+        app.world.send_event(WindowFocused {
+            window,
+            focused: true,
+        });
+        app.world.send_event(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window,
+        });
+        app.update();
+        assert_eq!(grab_mode(&app), CursorGrabMode::Locked);
+    }
+
+    #[test]
+    fn curves_can_be_edited_through_the_registered_component() {
+        let mut app = test_app(Duration::from_millis(100));
+        let cam = app
+            .world
+            .spawn(ThirdPersonCamera {
+                rigs: Some(CameraRigs::default()),
+                ..default()
+            })
+            .id();
+
+        // the way an inspector edits the camera, knowing only its type
+        let registry = app.world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let reflect_component = registry
+            .get_type_data::<ReflectComponent>(TypeId::of::<ThirdPersonCamera>())
+            .unwrap();
+        let mut entity = app.world.entity_mut(cam);
+        let mut reflected = reflect_component.reflect_mut(&mut entity).unwrap();
+        reflected
+            .reflect_path_mut("rigs.0.blend")
+            .unwrap()
+            .apply(&DisplacementCurve::Smoothstep);
+
+        let cam = app.world.get::<ThirdPersonCamera>(cam).unwrap();
+        let blend = &cam.rigs.as_ref().unwrap().blend;
+        assert!(matches!(blend, DisplacementCurve::Smoothstep));
+    }
+}
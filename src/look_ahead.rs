@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use crate::{smooth, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+/// Moves the focus ahead of the target in the direction it is moving, so players see what's
+/// coming. The lead is `time` seconds of the target's velocity along the ground, limited to
+/// `max_distance` and smoothed by `damping`. The velocity is measured from the target's
+/// [`GlobalTransform`], or read from [`TargetVelocity`] if the target has it
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{LookAhead, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     let mut look_ahead = LookAhead::default();
+///     look_ahead.max_distance = 1.0;
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             look_ahead: Some(look_ahead),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct LookAhead {
+    /// Seconds of velocity to lead by
+    pub time: f32,
+    /// Maximum distance the focus leads the target
+    pub max_distance: f32,
+    /// How quickly the lead follows changes in velocity. Higher values are snappier
+    pub damping: f32,
+    lead: Vec3,
+    last_position: Option<Vec3>,
+}
+
+impl Default for LookAhead {
+    fn default() -> Self {
+        Self {
+            time: 0.3,
+            max_distance: 2.0,
+            damping: 3.0,
+            lead: Vec3::ZERO,
+            last_position: None,
+        }
+    }
+}
+
+impl LookAhead {
+    /// How far the focus currently leads the target
+    pub fn lead(&self) -> Vec3 {
+        self.lead
+    }
+}
+
+/// Velocity of the [`ThirdPersonCameraTarget`], for example from a physics engine. When present
+/// [`LookAhead`] uses it instead of measuring the velocity from the target's movement
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{TargetVelocity, ThirdPersonCameraTarget};
+/// fn copy_velocity(mut target_q: Query<&mut TargetVelocity, With<ThirdPersonCameraTarget>>) {
+///     for mut velocity in target_q.iter_mut() {
+///         velocity.0 = Vec3::new(0.0, 0.0, -6.0);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct TargetVelocity(pub Vec3);
+
+// only run look ahead logic if `look_ahead` is set
+pub(crate) fn look_ahead_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.look_ahead.is_some()
+}
+
+// leads the focus by the target's velocity
+pub(crate) fn look_ahead(
+    mut cam_q: Query<&mut ThirdPersonCamera>,
+    target_q: Query<(&GlobalTransform, Option<&TargetVelocity>), With<ThirdPersonCameraTarget>>,
+    time: Res<Time>,
+) {
+    let Ok(mut cam) = cam_q.get_single_mut() else {
+        return;
+    };
+    let Ok((target, target_velocity)) = target_q.get_single() else {
+        return;
+    };
+
+    let up = cam.up.current();
+    let delta_seconds = time.delta_seconds();
+    let look_ahead = cam.look_ahead.as_mut().unwrap();
+
+    let position = target.translation();
+    let last_position = look_ahead.last_position.replace(position);
+    if delta_seconds > 0.0 {
+        let velocity = match (target_velocity, last_position) {
+            (Some(velocity), _) => velocity.0,
+            (None, Some(last)) => (position - last) / delta_seconds,
+            (None, None) => Vec3::ZERO,
+        };
+
+        // only lead along the ground, so jumping and falling don't move the view
+        let desired = (velocity.reject_from(up) * look_ahead.time.max(0.0))
+            .clamp_length_max(look_ahead.max_distance.max(0.0));
+        look_ahead.lead = smooth(look_ahead.lead, desired, look_ahead.damping, delta_seconds);
+    }
+
+    let lead = look_ahead.lead;
+    cam.focus += lead;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{test_app, LookAhead, TargetVelocity, ThirdPersonCamera, ThirdPersonCameraTarget};
+
+    #[test]
+    fn leads_by_measured_or_reported_velocity() {
+        let mut app = test_app(Duration::from_millis(100));
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::ZERO),
+            ))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    look_ahead: Some(LookAhead::default()),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        let lead = |app: &App| {
+            let cam = app.world.get::<ThirdPersonCamera>(cam).unwrap();
+            cam.focus - cam.true_focus
+        };
+        app.update();
+
+        // sprinting along +x at 5 units per second leads by 1.5 units once settled
+        for step in 1..=40 {
+            *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+                GlobalTransform::from_translation(Vec3::X * 0.5 * step as f32);
+            app.update();
+        }
+        assert!(lead(&app).abs_diff_eq(Vec3::X * 1.5, 1e-2));
+
+        // a reported velocity is used instead, and the lead is limited to `max_distance`
+        app.world
+            .entity_mut(target)
+            .insert(TargetVelocity(Vec3::NEG_Z * 20.0));
+        for _ in 0..40 {
+            app.update();
+        }
+        assert!(lead(&app).abs_diff_eq(Vec3::NEG_Z * 2.0, 1e-2));
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+/// What the camera looks towards with [`LookAt`]
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+pub enum LookAtTarget {
+    /// The translation of this entity's [`GlobalTransform`]
+    Entity(Entity),
+    /// A point in world space
+    Point(Vec3),
+}
+
+/// Turns the view towards something other than the focus, such as a boss or a door, while the
+/// camera keeps orbiting the target. `weight` blends from looking at the focus, at 0, to looking
+/// at the look at target, at 1. The look at target is placed where the focus would be on screen,
+/// so the shoulder offset is kept. Only the camera's [`GlobalTransform`] is turned, its
+/// [`Transform`] keeps the orbit rotation so orbiting, [`CameraBasis`](crate::CameraBasis)
+/// movement and target facing aren't affected. The look at is ignored while aiming, so the
+/// reticle, [`CameraAim`](crate::CameraAim) and aim assist all follow the same view
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{LookAt, LookAtTarget, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     let boss = commands.spawn(SpatialBundle::default()).id();
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             look_at: Some(LookAt {
+///                 target: LookAtTarget::Entity(boss),
+///                 // look halfway between the player and the boss
+///                 weight: 0.5,
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+pub struct LookAt {
+    pub target: LookAtTarget,
+    /// From 0 to 1, how far the view turns from the focus towards the look at target
+    pub weight: f32,
+}
+
+impl LookAt {
+    // the rotation of a camera at `translation` with `rotation` around `focus`, turned towards
+    // `point` by `weight`. The point ends up where the focus was on screen
+    pub(crate) fn rotation(
+        &self,
+        translation: Vec3,
+        rotation: Quat,
+        focus: Vec3,
+        point: Vec3,
+        up: Vec3,
+    ) -> Quat {
+        let (Some(to_focus), Some(to_point)) = (
+            (focus - translation).try_normalize(),
+            (point - translation).try_normalize(),
+        ) else {
+            return rotation;
+        };
+
+        // direction of the focus in the camera's own space, off center by the shoulder offset
+        let local_focus = rotation.inverse() * to_focus;
+        let looking = Transform::default().looking_to(to_point, up).rotation;
+        let looking = looking * Quat::from_rotation_arc(local_focus, Vec3::NEG_Z);
+        rotation.slerp(looking, self.weight.clamp(0.0, 1.0))
+    }
+}
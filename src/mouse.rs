@@ -1,12 +1,113 @@
-use std::f32::consts::PI;
-
 use bevy::{
-    input::mouse::{MouseMotion, MouseWheel},
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
     prelude::*,
-    window::PrimaryWindow,
 };
 
-use crate::{zoom_condition, ThirdPersonCamera};
+use crate::{orbit, window::CameraWindows, zoom, zoom_condition, ThirdPersonCamera};
+
+// number of pixels of touchpad or high resolution wheel scrolling treated as one line of scrolling
+const PIXELS_PER_LINE: f32 = 100.0;
+
+/// How far the camera turns for mouse motion. Measured per count of motion rather than per
+/// pixel, so it is the same at any window size or resolution
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{MouseAcceleration, MouseSensitivity, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             mouse_sensitivity: MouseSensitivity {
+///                 x: 0.15,
+///                 y: 0.1,
+///                 aim_multiplier: 0.5,
+///                 acceleration: Some(MouseAcceleration::default()),
+///             },
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseSensitivity {
+    /// Degrees the camera yaws per count of horizontal motion
+    pub x: f32,
+    /// Degrees the camera pitches per count of vertical motion
+    pub y: f32,
+    /// Multiplies the sensitivity while aiming
+    pub aim_multiplier: f32,
+    pub acceleration: Option<MouseAcceleration>,
+}
+
+impl Default for MouseSensitivity {
+    fn default() -> Self {
+        Self {
+            x: 0.15,
+            y: 0.15,
+            aim_multiplier: 1.0,
+            acceleration: None,
+        }
+    }
+}
+
+/// Raises the sensitivity when the mouse moves quickly. Above `offset` counts per second, the
+/// sensitivity grows by `gain` for every 1000 counts per second, up to `max` times the base
+/// sensitivity. Speed is measured per second, so acceleration is the same at any frame rate
+///
+/// ```
+/// use bevy_third_person_camera::MouseAcceleration;
+/// let acceleration = MouseAcceleration {
+///     offset: 500.0,
+///     gain: 0.5,
+///     max: 2.0,
+/// };
+/// assert_eq!(acceleration.multiplier(400.0), 1.0);
+/// assert_eq!(acceleration.multiplier(1500.0), 1.5);
+/// assert_eq!(acceleration.multiplier(10000.0), 2.0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseAcceleration {
+    /// Counts per second below which there is no acceleration
+    pub offset: f32,
+    /// Extra sensitivity, as a fraction of the base sensitivity, per 1000 counts per second
+    pub gain: f32,
+    /// Largest multiplier of the base sensitivity
+    pub max: f32,
+}
+
+impl Default for MouseAcceleration {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            gain: 0.5,
+            max: 2.0,
+        }
+    }
+}
+
+impl MouseAcceleration {
+    /// Sensitivity multiplier at a speed in counts per second
+    pub fn multiplier(&self, speed: f32) -> f32 {
+        let extra = (speed - self.offset).max(0.0) / 1000.0 * self.gain;
+        (1.0 + extra).clamp(1.0, self.max.max(1.0))
+    }
+}
+
+impl MouseSensitivity {
+    // turns a frame's mouse motion, in counts, into yaw & pitch in radians
+    fn radians(&self, counts: Vec2, delta_seconds: f32, aiming: bool) -> Vec2 {
+        let mut scale = if aiming { self.aim_multiplier } else { 1.0 };
+        if let (Some(acceleration), true) = (self.acceleration, delta_seconds > 0.0) {
+            scale *= acceleration.multiplier(counts.length() / delta_seconds);
+        }
+        counts * Vec2::new(self.x.to_radians(), self.y.to_radians()) * scale
+    }
+}
 
 pub struct MousePlugin;
 
@@ -28,72 +129,148 @@ fn orbit_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
     let Ok(cam) = cam_q.get_single() else {
         return true;
     };
-    return cam.cursor_lock_active;
+    cam.cursor_lock_active
 }
 
 // heavily referenced https://bevy-cheatbook.github.io/cookbook/pan-orbit-camera.html
+// every motion event of the frame is summed, so the camera turns the same amount for the same
+// physical mouse movement regardless of frame rate or window size. See `MouseSensitivity`
 pub fn orbit_mouse(
-    window_q: Query<&Window, With<PrimaryWindow>>,
-    mut cam_q: Query<(&ThirdPersonCamera, &mut Transform), With<ThirdPersonCamera>>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, Option<&Camera>, &mut Transform)>,
     mouse: Res<Input<MouseButton>>,
     mut mouse_evr: EventReader<MouseMotion>,
+    time: Res<Time>,
+    windows: CameraWindows,
 ) {
     let mut rotation = Vec2::ZERO;
     for ev in mouse_evr.read() {
-        rotation = ev.delta;
+        rotation += ev.delta;
     }
 
-    let Ok((cam, mut cam_transform)) = cam_q.get_single_mut() else {
+    let Ok((mut cam, camera, mut cam_transform)) = cam_q.get_single_mut() else {
         return;
     };
 
+    // motion events aren't tied to a window, only use them while the camera's window is focused
+    if !windows.takes_input(camera) {
+        rotation = Vec2::ZERO;
+    }
+
     if cam.mouse_orbit_button_enabled && !mouse.pressed(cam.mouse_orbit_button) {
         return;
     }
 
-    rotation *= cam.mouse_sensitivity;
+    rotation = cam.mouse_sensitivity.radians(
+        cam.mouse_invert.apply(rotation),
+        time.delta_seconds(),
+        cam.aim_state.is_active(),
+    );
 
     if rotation.length_squared() > 0.0 {
-        let window = window_q.get_single().unwrap();
-        let delta_x = rotation.x / window.width() * std::f32::consts::PI;
-        let delta_y = rotation.y / window.height() * PI;
-        let yaw = Quat::from_rotation_y(-delta_x);
-        let pitch = Quat::from_rotation_x(-delta_y);
-        cam_transform.rotation = yaw * cam_transform.rotation; // rotate around global y axis
-
-        // Calculate the new rotation without applying it to the camera yet
-        let new_rotation = cam_transform.rotation * pitch;
-
-        // check if new rotation will cause camera to go beyond the 180 degree vertical bounds
-        let up_vector = new_rotation * Vec3::Y;
-        if up_vector.y > 0.0 {
-            cam_transform.rotation = new_rotation;
+        if let Some(chase) = cam.chase.as_mut() {
+            chase.free_look();
         }
+        orbit(
+            &mut cam_transform.rotation,
+            cam.up.current(),
+            -rotation.x,
+            -rotation.y,
+        );
     }
-    let aim_btn = mouse.pressed(cam.aim_button);
-
-    let rot_matrix = Mat3::from_quat(cam_transform.rotation);
-    // apply the offset if offset_enabled is true and the user is aiming currently
-    let mut offset = Vec3::ZERO;
-    if cam.offset_enabled && aim_btn {
-        offset = rot_matrix.mul_vec3(Vec3::new(cam.offset.offset.0, cam.offset.offset.1, 0.0));
-    }
-
-    cam_transform.translation =
-        cam.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, cam.zoom.radius)) + offset;
 }
 
-fn zoom_mouse(mut scroll_evr: EventReader<MouseWheel>, mut cam_q: Query<&mut ThirdPersonCamera>) {
+// scrolling is measured in lines, pixel scrolling from touchpads and high resolution wheels is
+// converted using `PIXELS_PER_LINE`
+fn zoom_mouse(
+    mut scroll_evr: EventReader<MouseWheel>,
+    mut cam_q: Query<(&mut ThirdPersonCamera, Option<&Camera>)>,
+    windows: CameraWindows,
+) {
+    let Ok((mut cam, camera)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    // only scroll over the window the camera renders to zooms it
+    let window = windows.window(camera);
     let mut scroll = 0.0;
     for ev in scroll_evr.read() {
-        scroll += ev.y;
+        if window.is_some_and(|window| window != ev.window) {
+            continue;
+        }
+        scroll += match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / PIXELS_PER_LINE,
+        };
+    }
+
+    if scroll.abs() > 0.0 {
+        zoom(&mut cam, scroll);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        prelude::*,
+    };
+
+    use crate::{test_app, ThirdPersonCamera};
 
-    if let Ok(mut cam) = cam_q.get_single_mut() {
-        if scroll.abs() > 0.0 {
-            let new_radius =
-                cam.zoom.radius - scroll * cam.zoom.radius * 0.1 * cam.zoom_sensitivity;
-            cam.zoom.radius = new_radius.clamp(cam.zoom.min, cam.zoom.max);
+    // moves the mouse 240 times over one second, polled at the given frame rate
+    fn pose_after_moving_mouse(fps: u32) -> Transform {
+        let mut app = test_app(Duration::from_secs_f32(1.0 / fps as f32));
+        let cam = app
+            .world
+            .spawn((ThirdPersonCamera::default(), Transform::default()))
+            .id();
+        for _ in 0..fps {
+            for _ in 0..240 / fps {
+                app.world.send_event(MouseMotion {
+                    delta: Vec2::new(2.0, 0.5),
+                });
+            }
+            app.update();
         }
+        *app.world.get::<Transform>(cam).unwrap()
+    }
+
+    #[test]
+    fn orbit_is_frame_rate_independent() {
+        let (slow, fast) = (pose_after_moving_mouse(30), pose_after_moving_mouse(240));
+        assert!(slow.rotation.abs_diff_eq(fast.rotation, 1e-4));
+        assert!(slow.translation.abs_diff_eq(fast.translation, 1e-4));
+    }
+
+    fn distance_after_scrolling(unit: MouseScrollUnit, y: f32) -> f32 {
+        let mut app = test_app(Duration::from_millis(100));
+        let cam = app
+            .world
+            .spawn((ThirdPersonCamera::default(), Transform::default()))
+            .id();
+        app.world.send_event(MouseWheel {
+            unit,
+            y,
+            x: 0.0,
+            window: Entity::PLACEHOLDER,
+        });
+        // the camera is placed at the new radius on the frame after the scroll
+        app.update();
+        app.update();
+        app.world
+            .get::<Transform>(cam)
+            .unwrap()
+            .translation
+            .length()
+    }
+
+    #[test]
+    fn zoom_treats_lines_and_pixels_alike() {
+        let line = distance_after_scrolling(MouseScrollUnit::Line, 1.0);
+        let pixels = distance_after_scrolling(MouseScrollUnit::Pixel, 100.0);
+        assert!((line - pixels).abs() < 1e-4);
+        assert!(line < distance_after_scrolling(MouseScrollUnit::Line, 0.0));
     }
 }
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::{
+    gamepad::orbit_gamepad, mouse::orbit_mouse, ThirdPersonCamera, ThirdPersonCameraTarget,
+};
+
+pub struct MovementPlugin;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            publish_camera_basis.after(orbit_mouse).after(orbit_gamepad),
+        );
+    }
+}
+
+/// The camera's forward and right directions flattened onto the ground, kept up to date on the
+/// [`ThirdPersonCameraTarget`] so it can be moved relative to the camera
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::CameraBasis;
+/// fn player_movement(
+///     time: Res<Time>,
+///     keys: Res<Input<KeyCode>>,
+///     mut player_q: Query<(&mut Transform, &CameraBasis)>,
+/// ) {
+///     for (mut player_transform, basis) in player_q.iter_mut() {
+///         let mut input = Vec2::ZERO;
+///         if keys.pressed(KeyCode::W) {
+///             input.y += 1.0;
+///         }
+///         if keys.pressed(KeyCode::D) {
+///             input.x += 1.0;
+///         }
+///         player_transform.translation += basis.move_direction(input) * time.delta_seconds();
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct CameraBasis {
+    /// Direction the camera faces, flattened onto the ground and normalized
+    pub forward: Vec3,
+    /// Direction to the camera's right, flattened onto the ground and normalized
+    pub right: Vec3,
+}
+
+impl Default for CameraBasis {
+    fn default() -> Self {
+        Self {
+            forward: Vec3::NEG_Z,
+            right: Vec3::X,
+        }
+    }
+}
+
+impl CameraBasis {
+    /// Builds the basis from a camera's rotation
+    pub fn from_rotation(rotation: Quat) -> Self {
+        Self::from_rotation_and_up(rotation, Vec3::Y)
+    }
+
+    /// Builds the basis from a camera's rotation, flattened onto the ground below `up`
+    pub fn from_rotation_and_up(rotation: Quat, up: Vec3) -> Self {
+        // when looking straight down the camera's up points the way it is facing
+        let forward = (rotation * Vec3::NEG_Z).reject_from(up);
+        let forward = forward
+            .try_normalize()
+            .unwrap_or_else(|| (rotation * Vec3::Y).reject_from(up).normalize());
+        Self {
+            forward,
+            right: forward.cross(up),
+        }
+    }
+
+    /// Turns a 2D input, such as WASD or a stick with `x` to the right and `y` forward, into a
+    /// world space direction relative to the camera. Inputs longer than 1, such as W and D held
+    /// together, are shortened to 1 so diagonal movement isn't faster
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_third_person_camera::CameraBasis;
+    /// // a camera turned to look down the x axis
+    /// let basis = CameraBasis::from_rotation(Quat::from_rotation_y(-std::f32::consts::FRAC_PI_2));
+    ///
+    /// assert!(basis.move_direction(Vec2::Y).abs_diff_eq(Vec3::X, 1e-6));
+    /// assert!(basis.move_direction(Vec2::X).abs_diff_eq(Vec3::Z, 1e-6));
+    /// assert!((basis.move_direction(Vec2::ONE).length() - 1.0).abs() < 1e-6);
+    /// assert!(basis.move_direction(Vec2::new(0.0, 0.5)).abs_diff_eq(Vec3::X * 0.5, 1e-6));
+    /// ```
+    pub fn move_direction(&self, input: Vec2) -> Vec3 {
+        let input = input.clamp_length_max(1.0);
+        self.right * input.x + self.forward * input.y
+    }
+}
+
+// keeps the target's `CameraBasis` in sync with the camera
+fn publish_camera_basis(
+    mut commands: Commands,
+    cam_q: Query<(&ThirdPersonCamera, &Transform)>,
+    mut target_q: Query<(Entity, Option<&mut CameraBasis>), With<ThirdPersonCameraTarget>>,
+) {
+    let Ok((cam, cam_transform)) = cam_q.get_single() else {
+        return;
+    };
+    let Ok((target, basis)) = target_q.get_single_mut() else {
+        return;
+    };
+
+    let new_basis = CameraBasis::from_rotation_and_up(cam_transform.rotation, cam.up.current());
+    match basis {
+        Some(mut basis) => {
+            if *basis != new_basis {
+                *basis = new_basis;
+            }
+        }
+        None => {
+            commands.entity(target).insert(new_basis);
+        }
+    }
+}
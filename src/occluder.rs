@@ -0,0 +1,279 @@
+use bevy::{prelude::*, render::primitives::Aabb};
+
+use crate::{
+    fade::MaterialFader, ground::clamp_to_ground, ThirdPersonCamera, ThirdPersonCameraSet,
+};
+
+pub struct OccluderPlugin;
+
+impl Plugin for OccluderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                fade_occluders.run_if(occluder_fade_condition),
+                unfade_occluders.run_if(not(occluder_fade_condition)),
+            )
+                .after(clamp_to_ground)
+                .in_set(ThirdPersonCameraSet),
+        );
+    }
+}
+
+/// Makes geometry that blocks the line of sight from the camera to the focus see-through, as an
+/// alternative to pulling the camera in front of it. Only entities with a [`CameraOccluder`] are
+/// faded. Setting `occluder_fade` to `None` makes faded occluders opaque again right away
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{OccluderFade, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             occluder_fade: Some(OccluderFade {
+///                 alpha: 0.3,
+///                 speed: 4.0,
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, Reflect)]
+pub struct OccluderFade {
+    /// Alpha, relative to the original material, that occluders fade down to
+    pub alpha: f32,
+    /// How much the alpha changes per second while fading down or back up
+    pub speed: f32,
+}
+
+impl Default for OccluderFade {
+    fn default() -> Self {
+        Self {
+            alpha: 0.3,
+            speed: 4.0,
+        }
+    }
+}
+
+/// Opts an entity, and the meshes below it, into being faded when it blocks the camera's view
+/// of the focus
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::CameraOccluder;
+/// fn spawn_wall(mut commands: Commands) {
+///     commands.spawn((
+///         PbrBundle::default(),
+///         CameraOccluder
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct CameraOccluder;
+
+// how faded an occluder currently is, 1 being fully opaque
+#[derive(Component)]
+struct OccluderAlpha(f32);
+
+// only run the occluder fade if `occluder_fade` is set
+fn occluder_fade_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.occluder_fade.is_some()
+}
+
+// checks if the segment from `start` to `end` passes through the bounding box. The segment is
+// moved into the box's local space so rotated and scaled boxes are handled too
+fn segment_intersects(start: Vec3, end: Vec3, aabb: &Aabb, transform: &GlobalTransform) -> bool {
+    let inverse = transform.affine().inverse();
+    let start = inverse.transform_point3(start) - Vec3::from(aabb.center);
+    let end = inverse.transform_point3(end) - Vec3::from(aabb.center);
+    let half_extents = Vec3::from(aabb.half_extents);
+    let dir = end - start;
+
+    // slab test, clipping the segment against each pair of faces
+    let (mut t_min, mut t_max) = (0.0_f32, 1.0_f32);
+    for axis in 0..3 {
+        if dir[axis].abs() < f32::EPSILON {
+            if start[axis].abs() > half_extents[axis] {
+                return false;
+            }
+            continue;
+        }
+        let t1 = (-half_extents[axis] - start[axis]) / dir[axis];
+        let t2 = (half_extents[axis] - start[axis]) / dir[axis];
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+        if t_min > t_max {
+            return false;
+        }
+    }
+    true
+}
+
+// fades occluders down while they block the line of sight and back up once they no longer do
+fn fade_occluders(
+    mut commands: Commands,
+    cam_q: Query<(&ThirdPersonCamera, &Transform)>,
+    mut occluder_q: Query<(Entity, Option<&mut OccluderAlpha>), With<CameraOccluder>>,
+    children_q: Query<&Children>,
+    bounds_q: Query<(&Aabb, &GlobalTransform)>,
+    mut fader: MaterialFader,
+    time: Res<Time>,
+) {
+    let Ok((cam, cam_transform)) = cam_q.get_single() else {
+        return;
+    };
+    let Some(fade) = cam.occluder_fade else {
+        return;
+    };
+    let start = cam_transform.translation;
+    let end = cam.focus;
+    let step = fade.speed * time.delta_seconds();
+
+    for (occluder, alpha) in occluder_q.iter_mut() {
+        let entities = || std::iter::once(occluder).chain(children_q.iter_descendants(occluder));
+
+        let blocking = entities().any(|entity| {
+            bounds_q
+                .get(entity)
+                .is_ok_and(|(aabb, transform)| segment_intersects(start, end, aabb, transform))
+        });
+
+        let current = alpha.as_ref().map_or(1.0, |alpha| alpha.0);
+        let new = if blocking {
+            (current - step).max(fade.alpha)
+        } else {
+            (current + step).min(1.0)
+        };
+        if new == current {
+            continue;
+        }
+
+        match alpha {
+            Some(mut alpha) if new < 1.0 => alpha.0 = new,
+            Some(_) => {
+                commands.entity(occluder).remove::<OccluderAlpha>();
+            }
+            None => {
+                commands.entity(occluder).insert(OccluderAlpha(new));
+            }
+        }
+
+        for entity in entities() {
+            fader.fade(entity, new);
+        }
+    }
+}
+
+// makes every faded occluder opaque again once `occluder_fade` is unset
+fn unfade_occluders(
+    mut commands: Commands,
+    occluder_q: Query<Entity, (With<CameraOccluder>, With<OccluderAlpha>)>,
+    children_q: Query<&Children>,
+    mut fader: MaterialFader,
+) {
+    for occluder in occluder_q.iter() {
+        commands.entity(occluder).remove::<OccluderAlpha>();
+        for entity in std::iter::once(occluder).chain(children_q.iter_descendants(occluder)) {
+            fader.fade(entity, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{prelude::*, render::primitives::Aabb};
+
+    use crate::{test_app, CameraOccluder, OccluderFade, ThirdPersonCamera};
+
+    #[test]
+    fn fades_only_occluders_between_the_camera_and_focus() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.add_plugins(AssetPlugin::default())
+            .init_asset::<StandardMaterial>();
+        let shared = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let mut spawn_wall = |x: f32| {
+            let wall = (
+                CameraOccluder,
+                Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                GlobalTransform::from_translation(Vec3::new(x, 0.0, 1.0)),
+                shared.clone(),
+            );
+            app.world.spawn(wall).id()
+        };
+        // the camera sits behind the focus on the z axis, so only the first wall is in the way
+        let blocking = spawn_wall(0.0);
+        let beside = spawn_wall(5.0);
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    occluder_fade: Some(OccluderFade::default()),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let handle = app.world.get::<Handle<StandardMaterial>>(blocking).unwrap();
+        let materials = app.world.resource::<Assets<StandardMaterial>>();
+        assert_eq!(materials.get(handle).unwrap().base_color.a(), 0.3);
+        assert_eq!(materials.get(&shared).unwrap().base_color.a(), 1.0);
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(beside),
+            Some(&shared)
+        );
+
+        // once the wall is out of the way it fades back up to the shared, opaque material
+        *app.world.get_mut::<GlobalTransform>(blocking).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(-5.0, 0.0, 1.0));
+        app.update();
+        let handle = app.world.get::<Handle<StandardMaterial>>(blocking).unwrap();
+        let materials = app.world.resource::<Assets<StandardMaterial>>();
+        assert!(materials.get(handle).unwrap().base_color.a() < 1.0);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(blocking),
+            Some(&shared)
+        );
+
+        // turning the fade off while the wall is faded restores it right away
+        *app.world.get_mut::<GlobalTransform>(blocking).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 1.0));
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_ne!(
+            app.world.get::<Handle<StandardMaterial>>(blocking),
+            Some(&shared)
+        );
+        app.world
+            .get_mut::<ThirdPersonCamera>(cam)
+            .unwrap()
+            .occluder_fade = None;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(blocking),
+            Some(&shared)
+        );
+    }
+}
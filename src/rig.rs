@@ -0,0 +1,288 @@
+use bevy::{prelude::*, render::camera::CameraUpdateSystem};
+
+use crate::{
+    position_camera, DisplacementCurve, ThirdPersonCamera, ThirdPersonCameraSet, MAX_PITCH,
+};
+
+pub struct RigPlugin;
+
+impl Plugin for RigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            rig_fov
+                .after(position_camera)
+                .before(CameraUpdateSystem)
+                .in_set(ThirdPersonCameraSet)
+                .run_if(rigs_condition),
+        );
+    }
+}
+
+/// How the camera is framed at one pitch of the orbit
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraRig {
+    /// Multiplies the zoom radius
+    pub radius: f32,
+    /// Height of the focus above the target's focus, along the camera's up
+    pub height: f32,
+    /// Shoulder offset, used in place of [`Offset`](crate::Offset) whenever it would be applied.
+    /// The x offset is still mirrored when toggling shoulders
+    pub offset: (f32, f32),
+    /// Vertical field of view in degrees, applied to a perspective [`Projection`]
+    pub fov: f32,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            height: 0.0,
+            offset: (0.5, 0.4),
+            fov: 45.0,
+        }
+    }
+}
+
+impl CameraRig {
+    // blends towards `other` by `t`, from 0 to 1
+    fn lerp(&self, other: &CameraRig, t: f32) -> CameraRig {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        CameraRig {
+            radius: lerp(self.radius, other.radius),
+            height: lerp(self.height, other.height),
+            offset: (
+                lerp(self.offset.0, other.offset.0),
+                lerp(self.offset.1, other.offset.1),
+            ),
+            fov: lerp(self.fov, other.fov),
+        }
+    }
+}
+
+/// A free look rig of three rings. `top` frames the camera at the highest pitch, looking down on
+/// the target, `middle` when level with it and `bottom` at the lowest pitch, looking up. In
+/// between, the rigs are blended along `blend`, which maps how far the camera is from level
+/// towards the top or bottom, from 0 to 1, to how much of that rig is used
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{CameraRig, CameraRigs, DisplacementCurve, ThirdPersonCamera};
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             rigs: Some(CameraRigs {
+///                 top: CameraRig {
+///                     radius: 1.3,
+///                     height: 0.4,
+///                     offset: (0.3, 0.2),
+///                     fov: 50.0,
+///                 },
+///                 middle: CameraRig::default(),
+///                 bottom: CameraRig {
+///                     radius: 0.6,
+///                     height: -0.2,
+///                     offset: (0.5, 0.1),
+///                     fov: 40.0,
+///                 },
+///                 blend: DisplacementCurve::Smoothstep,
+///             }),
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraRigs {
+    pub top: CameraRig,
+    pub middle: CameraRig,
+    pub bottom: CameraRig,
+    pub blend: DisplacementCurve,
+}
+
+impl Default for CameraRigs {
+    fn default() -> Self {
+        Self {
+            top: CameraRig::default(),
+            middle: CameraRig::default(),
+            bottom: CameraRig::default(),
+            blend: DisplacementCurve::Linear,
+        }
+    }
+}
+
+impl CameraRigs {
+    /// The rig at `pitch` radians, negative when the camera looks down
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_third_person_camera::{CameraRig, CameraRigs, DisplacementCurve};
+    /// use std::f32::consts::FRAC_PI_2;
+    /// let rigs = CameraRigs {
+    ///     top: CameraRig { radius: 2.0, ..default() },
+    ///     middle: CameraRig { radius: 1.0, ..default() },
+    ///     bottom: CameraRig { radius: 0.5, ..default() },
+    ///     blend: DisplacementCurve::Linear,
+    /// };
+    /// assert_eq!(rigs.sample(0.0).radius, 1.0);
+    /// assert_eq!(rigs.sample(-FRAC_PI_2).radius, 2.0);
+    /// assert_eq!(rigs.sample(FRAC_PI_2).radius, 0.5);
+    /// assert!((rigs.sample(-FRAC_PI_2 / 2.0).radius - 1.5).abs() < 0.01);
+    /// ```
+    pub fn sample(&self, pitch: f32) -> CameraRig {
+        let t = (pitch / MAX_PITCH).clamp(-1.0, 1.0);
+        if t < 0.0 {
+            self.middle.lerp(&self.top, self.blend.sample(-t))
+        } else {
+            self.middle.lerp(&self.bottom, self.blend.sample(t))
+        }
+    }
+
+    // the rig for a camera with `rotation`, pitched relative to `up`
+    pub(crate) fn sample_rotation(&self, rotation: Quat, up: Vec3) -> CameraRig {
+        let pitch = (rotation * Vec3::NEG_Z).dot(up).clamp(-1.0, 1.0).asin();
+        self.sample(pitch)
+    }
+}
+
+// only run rig logic if `rigs` is set
+pub(crate) fn rigs_condition(cam_q: Query<&ThirdPersonCamera>) -> bool {
+    let Ok(cam) = cam_q.get_single() else {
+        return false;
+    };
+    cam.rigs.is_some()
+}
+
+// raises the focus by the rig's height, the radius and offset are applied when placing the camera
+pub(crate) fn rig_focus(mut cam_q: Query<(&mut ThirdPersonCamera, &Transform)>) {
+    let Ok((mut cam, transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    let up = cam.up.current();
+    let rig = cam
+        .rigs
+        .as_ref()
+        .unwrap()
+        .sample_rotation(transform.rotation, up);
+    cam.focus += up * rig.height;
+}
+
+// sets the field of view of the rig at the camera's pitch
+fn rig_fov(mut cam_q: Query<(&ThirdPersonCamera, &Transform, &mut Projection)>) {
+    let Ok((cam, transform, mut projection)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    let rig = cam
+        .rigs
+        .as_ref()
+        .unwrap()
+        .sample_rotation(transform.rotation, cam.up.current());
+    let fov = rig.fov.to_radians();
+    // only write the projection when the fov changes, so its matrix isn't recomputed every frame
+    if matches!(projection.as_ref(), Projection::Perspective(perspective) if perspective.fov != fov)
+    {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = fov;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, CameraFocusModifier, CameraRig, CameraRigs, DisplacementCurve, ThirdPersonCamera,
+        ThirdPersonCameraTarget, Zoom,
+    };
+
+    #[test]
+    fn top_rig_raises_focus_scales_radius_and_sets_fov() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            GlobalTransform::from_translation(Vec3::ZERO),
+        ));
+        let rigs = CameraRigs {
+            top: CameraRig {
+                radius: 2.0,
+                height: 1.0,
+                fov: 60.0,
+                ..default()
+            },
+            ..default()
+        };
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    rigs: Some(rigs),
+                    zoom: Zoom::new(2.0, 2.0),
+                    ..default()
+                },
+                // looking straight down from above the target
+                Transform::default().looking_to(Vec3::NEG_Y, Vec3::NEG_Z),
+            ))
+            .id();
+        app.world
+            .entity_mut(cam)
+            .insert(Projection::Perspective(default()));
+        app.update();
+
+        // the focus is raised, the camera is twice as far and the field of view widened
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(0.0, 1.81, 0.0), 1e-4));
+        let translation = app.world.get::<Transform>(cam).unwrap().translation;
+        assert!(((translation - focus).dot(Vec3::Y) - 4.0).abs() < 0.01);
+        let Projection::Perspective(projection) = app.world.get::<Projection>(cam).unwrap() else {
+            unreachable!();
+        };
+        assert!((projection.fov.to_degrees() - 60.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn focus_modifier_is_ignored_while_rigs_are_set() {
+        let mut app = test_app(Duration::from_millis(100));
+        app.world.spawn((
+            ThirdPersonCameraTarget,
+            GlobalTransform::from_translation(Vec3::ZERO),
+        ));
+        // a modifier that would pull the camera in and push the focus back when looking up
+        let focus_modifier = CameraFocusModifier {
+            lower_threshold: FRAC_PI_2,
+            max_backward_displacement: 1.0,
+            lower_displacement_curve: DisplacementCurve::Constant(1.0),
+            behind_radius_displacement: 1.0,
+            lower_radius_curve: DisplacementCurve::Constant(1.0),
+            ..default()
+        };
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    focus_modifier: Some(focus_modifier),
+                    rigs: Some(CameraRigs::default()),
+                    zoom: Zoom::new(2.0, 2.0),
+                    ..default()
+                },
+                // pitched to look up at the focus
+                Transform::from_rotation(Quat::from_rotation_x(0.6)),
+            ))
+            .id();
+        app.update();
+
+        let cam_ref = app.world.get::<ThirdPersonCamera>(cam).unwrap();
+        assert_eq!(cam_ref.focus, cam_ref.true_focus);
+        let translation = app.world.get::<Transform>(cam).unwrap().translation;
+        assert!((translation.distance(cam_ref.focus) - 2.0).abs() < 1e-4);
+    }
+}
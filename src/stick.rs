@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+/// How the inner deadzone of a stick is measured
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeadzoneMode {
+    /// Ignores deflections shorter than the deadzone in any direction. Keeps diagonals smooth
+    #[default]
+    Radial,
+    /// Ignores each axis separately while it is inside the deadzone. Makes it easy to turn
+    /// horizontally without drifting vertically
+    Axial,
+}
+
+/// Shapes how stick deflection, after the deadzones, turns into speed. Inputs and outputs go from
+/// 0 to 1
+#[derive(Clone, Copy, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    /// Raises the deflection to this power. Values above 1 give finer control near the center
+    Exponential(f32),
+    /// Maps the deflection with a custom function. The function doesn't survive a round trip: it
+    /// can't be serialized, and a `Custom` curve built through reflection, for example cloned or
+    /// edited from an inspector, is linear
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(#[reflect(ignore, default = "crate::unreflected_response")] fn(f32) -> f32),
+}
+
+impl ResponseCurve {
+    // maps a deflection from 0 to 1 through the curve
+    fn apply(&self, value: f32) -> f32 {
+        let value = match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Exponential(exponent) => value.powf(*exponent),
+            ResponseCurve::Custom(curve) => curve(value),
+        };
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// Deadzones and response curve for a stick. With the `serde` feature they can be saved and loaded
+/// with the rest of a game's settings
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{DeadzoneMode, ResponseCurve, StickSettings};
+/// let stick = StickSettings {
+///     deadzone: 0.2,
+///     outer_deadzone: 0.1,
+///     mode: DeadzoneMode::Radial,
+///     rescale: true,
+///     curve: ResponseCurve::Linear,
+/// };
+///
+/// // inside the deadzone
+/// assert_eq!(stick.apply(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+/// // rescaled to start from zero at the edge of the deadzone
+/// assert!(stick.apply(Vec2::new(0.55, 0.0)).abs_diff_eq(Vec2::new(0.5, 0.0), 1e-6));
+/// // anything inside the outer deadzone is full deflection
+/// assert!(stick.apply(Vec2::new(0.0, -0.95)).abs_diff_eq(Vec2::new(0.0, -1.0), 1e-6));
+///
+/// let squared = StickSettings {
+///     curve: ResponseCurve::Exponential(2.0),
+///     ..stick
+/// };
+/// assert!(squared.apply(Vec2::new(0.55, 0.0)).abs_diff_eq(Vec2::new(0.25, 0.0), 1e-6));
+/// ```
+#[derive(Clone, Copy, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StickSettings {
+    /// Deflections smaller than this are ignored
+    pub deadzone: f32,
+    /// Deflections within this distance of the edge count as full deflection
+    pub outer_deadzone: f32,
+    pub mode: DeadzoneMode,
+    /// Rescales the deflection between the deadzones to go from 0 to 1, so the stick doesn't jump
+    /// when leaving the deadzone
+    pub rescale: bool,
+    pub curve: ResponseCurve,
+}
+
+impl Default for StickSettings {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.15,
+            outer_deadzone: 0.05,
+            mode: DeadzoneMode::default(),
+            rescale: true,
+            curve: ResponseCurve::default(),
+        }
+    }
+}
+
+impl StickSettings {
+    /// Applies the deadzones and response curve to a raw stick position
+    pub fn apply(&self, raw: Vec2) -> Vec2 {
+        match self.mode {
+            DeadzoneMode::Radial => {
+                let length = raw.length();
+                if length <= f32::EPSILON {
+                    return Vec2::ZERO;
+                }
+                raw / length * self.shape(length)
+            }
+            DeadzoneMode::Axial => Vec2::new(
+                raw.x.signum() * self.shape(raw.x.abs()),
+                raw.y.signum() * self.shape(raw.y.abs()),
+            ),
+        }
+    }
+
+    // turns a deflection length into the shaped deflection, from 0 to 1
+    fn shape(&self, length: f32) -> f32 {
+        if length <= self.deadzone {
+            return 0.0;
+        }
+        let outer = (1.0 - self.outer_deadzone).max(self.deadzone + f32::EPSILON);
+        let value = if length >= outer {
+            1.0
+        } else if self.rescale {
+            (length - self.deadzone) / (outer - self.deadzone)
+        } else {
+            length
+        };
+        self.curve.apply(value)
+    }
+}
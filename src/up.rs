@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+
+use crate::{ThirdPersonCamera, ThirdPersonCameraTarget};
+
+/// Answers which way is up at a point, for example away from the center of a planet. Implemented
+/// for [`PointGravity`] and for closures
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{GravityField, PointGravity};
+///
+/// let planet = PointGravity { center: Vec3::ZERO };
+/// assert_eq!(planet.up_at(Vec3::new(0.0, 0.0, 5.0)), Vec3::Z);
+///
+/// // gravity pulling towards a wall on the right
+/// let wall = |_position: Vec3| Vec3::NEG_X;
+/// assert_eq!(wall.up_at(Vec3::ZERO), Vec3::NEG_X);
+/// ```
+pub trait GravityField: Send + Sync + 'static {
+    /// Direction of up at `position`, opposite to the pull of gravity
+    fn up_at(&self, position: Vec3) -> Vec3;
+}
+
+impl<F> GravityField for F
+where
+    F: Fn(Vec3) -> Vec3 + Send + Sync + 'static,
+{
+    fn up_at(&self, position: Vec3) -> Vec3 {
+        self(position)
+    }
+}
+
+/// Gravity pulling towards `center`, such as a small planet
+#[derive(Clone, Copy, Default)]
+pub struct PointGravity {
+    pub center: Vec3,
+}
+
+impl GravityField for PointGravity {
+    fn up_at(&self, position: Vec3) -> Vec3 {
+        (position - self.center).normalize_or_zero()
+    }
+}
+
+/// Where the camera's up direction comes from
+pub enum UpSource {
+    /// A fixed direction, `Vec3::Y` by default
+    Fixed(Vec3),
+    /// The up direction of the [`ThirdPersonCameraTarget`]'s [`GlobalTransform`], for targets
+    /// that walk on walls or planets and align themselves
+    Target,
+    /// Up of a gravity field at the target's position
+    Gravity(Box<dyn GravityField>),
+}
+
+impl Default for UpSource {
+    fn default() -> Self {
+        UpSource::Fixed(Vec3::Y)
+    }
+}
+
+/// The camera's up direction. Orbiting, the pitch limits and the
+/// [`CameraFocusModifier`](crate::CameraFocusModifier) all work relative to it. When up changes
+/// the camera turns towards the new up at `realign_speed` degrees per second, keeping its view
+/// relative to the target
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_third_person_camera::{CameraUp, PointGravity, ThirdPersonCamera, UpSource};
+/// fn spawn_camera(mut commands: Commands) {
+///     let mut up = CameraUp::new(UpSource::Gravity(Box::new(PointGravity { center: Vec3::ZERO })));
+///     up.realign_speed = 180.0;
+///     commands.spawn((
+///         ThirdPersonCamera {
+///             up,
+///             ..default()
+///         },
+///         Camera3dBundle::default(),
+///     ));
+/// }
+/// ```
+#[derive(Reflect)]
+pub struct CameraUp {
+    /// Ignored by reflection
+    #[reflect(ignore)]
+    pub source: UpSource,
+    /// Degrees per second the camera turns to follow a change of up
+    pub realign_speed: f32,
+    current: Option<Vec3>,
+}
+
+impl Default for CameraUp {
+    fn default() -> Self {
+        Self::new(UpSource::default())
+    }
+}
+
+impl CameraUp {
+    pub fn new(source: UpSource) -> Self {
+        Self {
+            source,
+            realign_speed: 360.0,
+            current: None,
+        }
+    }
+
+    /// The up direction the camera is currently aligned to
+    pub fn current(&self) -> Vec3 {
+        self.current.unwrap_or(Vec3::Y)
+    }
+}
+
+// signed angle around `up` that turns `from` towards `to`, ignoring how high either points
+pub(crate) fn yaw_between(from: Vec3, to: Vec3, up: Vec3) -> f32 {
+    let (from, to) = (from.reject_from(up), to.reject_from(up));
+    if from.length_squared() <= f32::EPSILON || to.length_squared() <= f32::EPSILON {
+        return 0.0;
+    }
+    from.angle_between(to) * from.cross(to).dot(up).signum()
+}
+
+// turns the camera towards its new up direction, the first alignment happens immediately
+pub(crate) fn align_up(
+    mut cam_q: Query<(&mut ThirdPersonCamera, &mut Transform), Without<ThirdPersonCameraTarget>>,
+    target_q: Query<&GlobalTransform, With<ThirdPersonCameraTarget>>,
+    time: Res<Time>,
+) {
+    let Ok((mut cam, mut cam_transform)) = cam_q.get_single_mut() else {
+        return;
+    };
+    let target = target_q.get_single().ok();
+
+    let desired = match &cam.up.source {
+        UpSource::Fixed(up) => Some(*up),
+        UpSource::Target => target.map(|target| target.up()),
+        UpSource::Gravity(field) => target.map(|target| field.up_at(target.translation())),
+    };
+    let Some(desired) = desired.and_then(|up| up.try_normalize()) else {
+        return;
+    };
+
+    let new_up = match cam.up.current {
+        // the first up is taken as is
+        None => desired,
+        Some(current) => {
+            let angle = current.angle_between(desired);
+            let max_angle = cam.up.realign_speed.to_radians() * time.delta_seconds();
+            if angle <= max_angle {
+                desired
+            } else {
+                Quat::IDENTITY.slerp(Quat::from_rotation_arc(current, desired), max_angle / angle)
+                    * current
+            }
+        }
+    };
+
+    let current = cam.up.current();
+    if cam.up.current != Some(new_up) {
+        // turn the whole camera with its up so the view stays the same relative to the target
+        cam_transform.rotation = Quat::from_rotation_arc(current, new_up) * cam_transform.rotation;
+        cam.up.current = Some(new_up);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        test_app, CameraUp, PointGravity, ThirdPersonCamera, ThirdPersonCameraTarget, UpSource,
+    };
+
+    #[test]
+    fn up_follows_gravity_around_a_planet() {
+        let mut app = test_app(Duration::from_millis(100));
+        // the target stands on the side of a planet, where up is +x
+        let target = app
+            .world
+            .spawn((
+                ThirdPersonCameraTarget,
+                GlobalTransform::from_translation(Vec3::new(5.0, 0.0, 0.0)),
+            ))
+            .id();
+        let cam = app
+            .world
+            .spawn((
+                ThirdPersonCamera {
+                    up: CameraUp::new(UpSource::Gravity(Box::new(PointGravity {
+                        center: Vec3::ZERO,
+                    }))),
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.update();
+        let up = |app: &App| {
+            app.world
+                .get::<ThirdPersonCamera>(cam)
+                .unwrap()
+                .up
+                .current()
+        };
+        assert!(up(&app).abs_diff_eq(Vec3::X, 1e-4));
+        let transform = *app.world.get::<Transform>(cam).unwrap();
+        assert!(transform.up().abs_diff_eq(Vec3::X, 1e-4));
+        // the camera sits behind and level with the focus, relative to the new up
+        let focus = app.world.get::<ThirdPersonCamera>(cam).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(5.81, 0.0, 0.0), 1e-4));
+        assert!((transform.translation - focus).dot(Vec3::X).abs() < 1e-4);
+
+        // walking a quarter of the way around the planet turns up over the next half second
+        *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(0.0, 5.0, 0.0));
+        app.update();
+        assert!(up(&app).angle_between(Vec3::Y) > 0.5);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(up(&app).abs_diff_eq(Vec3::Y, 1e-4));
+    }
+}
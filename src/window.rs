@@ -0,0 +1,89 @@
+use bevy::{
+    ecs::system::SystemParam, prelude::*, render::camera::NormalizedRenderTarget,
+    window::PrimaryWindow,
+};
+
+// finds the window a camera renders to. `None` when it renders to an image or texture view, or
+// when it targets the primary window and there is none. Entities without a `Camera` are treated
+// as rendering to the primary window
+pub(crate) fn camera_window(
+    camera: Option<&Camera>,
+    primary_window: Option<Entity>,
+) -> Option<Entity> {
+    let Some(camera) = camera else {
+        return primary_window;
+    };
+    match camera.target.normalize(primary_window)? {
+        NormalizedRenderTarget::Window(window) => Some(window.entity()),
+        _ => None,
+    }
+}
+
+// resolves the window each camera renders to, so input and cursor handling follow
+// `Camera::target` instead of always using the `PrimaryWindow`
+#[derive(SystemParam)]
+pub(crate) struct CameraWindows<'w, 's> {
+    primary_q: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    window_q: Query<'w, 's, &'static Window>,
+}
+
+impl CameraWindows<'_, '_> {
+    pub(crate) fn window(&self, camera: Option<&Camera>) -> Option<Entity> {
+        camera_window(camera, self.primary_q.get_single().ok())
+    }
+
+    // cameras only take input while the window they render to is focused. Cameras rendering to
+    // an image, or running without any window at all, always take input
+    pub(crate) fn takes_input(&self, camera: Option<&Camera>) -> bool {
+        match self
+            .window(camera)
+            .and_then(|window| self.window_q.get(window).ok())
+        {
+            Some(window) => window.focused,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        input::mouse::MouseMotion, prelude::*, render::camera::RenderTarget, window::WindowRef,
+    };
+
+    use crate::{test_app, ThirdPersonCamera};
+
+    #[test]
+    fn ignores_input_while_its_window_is_in_the_background() {
+        let mut app = test_app(Duration::from_millis(100));
+        let window = app
+            .world
+            .spawn(Window {
+                focused: false,
+                ..default()
+            })
+            .id();
+        let cam = app
+            .world
+            .spawn((ThirdPersonCamera::default(), Transform::default()))
+            .id();
+        app.world.entity_mut(cam).insert(Camera {
+            target: RenderTarget::Window(WindowRef::Entity(window)),
+            ..default()
+        });
+        let rotation_after_moving = |app: &mut App| {
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(50.0, 0.0),
+            });
+            app.update();
+            app.world.get::<Transform>(cam).unwrap().rotation
+        };
+
+        assert_eq!(rotation_after_moving(&mut app), Quat::IDENTITY);
+
+        app.world.get_mut::<Window>(window).unwrap().focused = true;
+        assert_ne!(rotation_after_moving(&mut app), Quat::IDENTITY);
+    }
+}